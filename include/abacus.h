@@ -0,0 +1,66 @@
+/* Expression evaluation for C and C++, built as the cdylib librust_abacus.
+ *
+ *     AbacusExecutor *executor = abacus_create();
+ *     char *result = NULL;
+ *     abacus_set_variable(executor, "price", "120");
+ *     if (abacus_evaluate(executor, "price * 1.2", &result) == ABACUS_OK) {
+ *         puts(result);
+ *         abacus_free_string(result);
+ *     } else {
+ *         fprintf(stderr, "%s\n", abacus_last_error(executor));
+ *     }
+ *     abacus_free(executor);
+ *
+ * An executor is not thread safe, use one per thread. Errors never exit the
+ * process: every call returns a status and a failed call leaves the variables
+ * as they were.
+ */
+#ifndef ABACUS_H
+#define ABACUS_H
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* statuses, from 1 to 7 they are the errors of the calculator */
+#define ABACUS_OK 0
+#define ABACUS_SYNTAX_ERROR 1
+#define ABACUS_UNEXPECTED_TOKEN 2
+#define ABACUS_INVALID_OPERATION 3
+#define ABACUS_UNBOUND_VARIABLE 4
+#define ABACUS_SHAPE_MISMATCH 5
+#define ABACUS_DIMENSION_MISMATCH 6
+#define ABACUS_TYPE_ERROR 7
+/* a null pointer, a string which is not UTF-8 or a name which is not a variable */
+#define ABACUS_INVALID_ARGUMENT 8
+/* a bug of the calculator or of a registered function */
+#define ABACUS_INTERNAL_ERROR 9
+
+typedef struct AbacusExecutor AbacusExecutor;
+
+AbacusExecutor *abacus_create(void);
+void abacus_free(AbacusExecutor *executor);
+
+/* Evaluate the statements of input. When result is not NULL it receives the
+ * value of the last one as text, or NULL on error, to be released with
+ * abacus_free_string. */
+int abacus_evaluate(AbacusExecutor *executor, const char *input, char **result);
+
+/* Bind name to the value of an expression, e.g. "2.5", "1/3" or "\"text\"".
+ * Several statements or an assignment in value are a syntax error. */
+int abacus_set_variable(AbacusExecutor *executor, const char *name, const char *value);
+
+/* Value of a variable as text, released with abacus_free_string. */
+int abacus_get_variable(AbacusExecutor *executor, const char *name, char **value);
+
+void abacus_free_string(char *text);
+
+/* Message of the error returned by the last call on executor, empty after a
+ * success. It is valid until the next call on executor. */
+const char *abacus_last_error(const AbacusExecutor *executor);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,52 @@
+/* Function plugins for rust_abacus, loaded with --plugins <directory>.
+ *
+ * A plugin is a shared library (.so) exporting abacus_plugin, which returns
+ * the table of its functions. The table and the names must stay valid while
+ * the library is loaded, static storage is the simplest.
+ *
+ *     static int tax(const double *args, uint32_t count, double *result) {
+ *         *result = args[0] * 0.2;
+ *         return 0;
+ *     }
+ *
+ *     static const AbacusPluginFunction functions[] = {{"tax", 1, tax}};
+ *     static const AbacusPlugin plugin = {ABACUS_PLUGIN_VERSION, 1, functions};
+ *
+ *     const AbacusPlugin *abacus_plugin(void) { return &plugin; }
+ */
+#ifndef ABACUS_PLUGIN_H
+#define ABACUS_PLUGIN_H
+
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define ABACUS_PLUGIN_VERSION 1
+
+/* Called with exactly arity arguments, writes the result and returns 0.
+ * Any other status is reported as an invalid operation. */
+typedef int (*AbacusPluginCall)(const double *args, uint32_t count, double *result);
+
+typedef struct {
+    /* an identifier which is neither a keyword nor a function already there */
+    const char *name;
+    uint32_t arity;
+    AbacusPluginCall call;
+} AbacusPluginFunction;
+
+typedef struct {
+    /* ABACUS_PLUGIN_VERSION, a plugin built against another version is refused */
+    uint32_t version;
+    uint32_t count;
+    const AbacusPluginFunction *functions;
+} AbacusPlugin;
+
+const AbacusPlugin *abacus_plugin(void);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
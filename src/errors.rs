@@ -1,12 +1,15 @@
 use std::process::exit;
-use crate::errors::ErrorType::{InvalidOperation, SyntaxError, UnboundVariable, UnexpectedToken};
+use crate::errors::ErrorType::{DimensionMismatch, InvalidOperation, ShapeMismatch, SyntaxError, TypeError, UnboundVariable, UnexpectedToken};
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Copy)]
 pub enum ErrorType {
     SyntaxError,
     UnexpectedToken,
     InvalidOperation,
     UnboundVariable,
+    ShapeMismatch,
+    DimensionMismatch,
+    TypeError,
 }
 
 struct Errors {
@@ -21,20 +24,39 @@ impl Default for Errors {
                 (UnexpectedToken, "Unexpected token.".to_string(), 2),
                 (InvalidOperation, "Invalid operation.".to_string(), 3),
                 (UnboundVariable, "Unbound variable.".to_string(), 3),
+                (ShapeMismatch, "Shape mismatch.".to_string(), 3),
+                (DimensionMismatch, "Dimension mismatch.".to_string(), 3),
+                (TypeError, "Type error.".to_string(), 3),
             ]
         }
     }
 }
 
-pub struct Error;
-impl Error {
-    pub fn throw<T>(error_type: ErrorType) -> T {
-
+impl Errors {
+    fn find(error_type: ErrorType) -> (String, i8) {
         let errors = Errors::default();
 
         let error: (ErrorType, String, i8) = errors.errors.into_iter().find(|(error_type_, _, _)| *error_type_ == error_type).unwrap();
         let (_, error_message, error_code) = error;
+        (error_message, error_code)
+    }
+}
+
+pub struct Error;
+impl Error {
+    //fail the current evaluation, the error is returned up to whoever reports it
+    pub fn throw<T>(error_type: ErrorType) -> Result<T, ErrorType> {
+        Err(error_type)
+    }
+
+    pub fn message(error_type: ErrorType) -> String {
+        Errors::find(error_type).0
+    }
+
+    //print the error and leave with its exit code
+    pub fn exit(error_type: ErrorType) -> ! {
+        let (error_message, error_code) = Errors::find(error_type);
         eprintln!("{}", error_message);
         exit(error_code as i32);
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+use crate::value::Value;
+
+//chain of scopes on top of the global variables
+//globals are shared copy-on-write so taking a snapshot never copies them
+#[derive(Clone, Default)]
+pub struct Environment {
+    globals: Rc<HashMap<String, Value>>,
+    scopes: Vec<HashMap<String, Value>>,
+}
+
+//saved state of the global variables, see Environment::snapshot
+#[derive(Clone)]
+pub struct Snapshot {
+    globals: Rc<HashMap<String, Value>>,
+}
+
+impl Environment {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    //look a name up from the innermost scope to the globals
+    pub fn get(&self, name: &str) -> Option<Value> {
+        self.scopes.iter().rev()
+            .find_map(|scope| scope.get(name))
+            .or_else(|| self.globals.get(name))
+            .cloned()
+    }
+
+    //update the closest existing binding, or create a global one
+    pub fn assign(&mut self, name: &str, value: Value) {
+        match self.scopes.iter_mut().rev().find_map(|scope| scope.get_mut(name)) {
+            Some(binding) => *binding = value,
+            None => { Rc::make_mut(&mut self.globals).insert(name.to_string(), value); }
+        }
+    }
+
+    //bind a name in the innermost scope, shadowing outer bindings
+    pub fn define(&mut self, name: &str, value: Value) {
+        match self.scopes.last_mut() {
+            Some(scope) => { scope.insert(name.to_string(), value); }
+            None => { Rc::make_mut(&mut self.globals).insert(name.to_string(), value); }
+        }
+    }
+
+    pub fn push_scope(&mut self) {
+        self.scopes.push(HashMap::new());
+    }
+
+    pub fn pop_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    //drop the scopes left open by an evaluation which failed inside them
+    pub fn close_scopes(&mut self) {
+        self.scopes.clear();
+    }
+
+    //global bindings sorted by name
+    pub fn globals(&self) -> Vec<(&String, &Value)> {
+        let mut globals: Vec<(&String, &Value)> = self.globals.iter().collect();
+        globals.sort_by(|a, b| a.0.cmp(b.0));
+        globals
+    }
+
+    //copy of the global bindings, to read the results back after executing
+    pub fn bindings(&self) -> HashMap<String, Value> {
+        self.globals.as_ref().clone()
+    }
+
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot { globals: Rc::clone(&self.globals) }
+    }
+
+    //go back to the globals of a snapshot, local scopes are dropped
+    pub fn restore(&mut self, snapshot: Snapshot) {
+        self.globals = snapshot.globals;
+        self.scopes.clear();
+    }
+}
+
+impl FromIterator<(String, Value)> for Environment {
+    fn from_iter<T: IntoIterator<Item = (String, Value)>>(iter: T) -> Self {
+        Self { globals: Rc::new(iter.into_iter().collect()), scopes: Vec::new() }
+    }
+}
@@ -1,34 +1,143 @@
+pub mod environment;
+mod operations;
+pub mod session;
+
 use std::collections::VecDeque;
+use std::slice;
 use crate::errors::{Error, ErrorType};
+use crate::executor::environment::Environment;
+use crate::functions::{Function, FunctionRegistry};
+use crate::lexer::Lexer;
+use crate::lexer::shunting_yard::ShuntingYard;
 use crate::lexer::tokens::{TokenTrait, TokenType};
-use crate::lexer::tokens::binary_operator_token::TokenOperator;
-use crate::lexer::tokens::function_token::TokenFunction;
+use crate::lexer::tokens::binary_operator_token::{TokenOperator, TokenOperatorValue};
+use crate::lexer::tokens::function_token::{DefinedFunction, TokenFunction};
+use crate::maths::Math;
+use crate::symbolic::Expr;
+use crate::value::complex::Complex;
+use crate::value::date::Date;
+use crate::value::decimal::{Decimal, Precision};
+use crate::value::interval::Interval;
+use crate::value::quantity::Quantity;
+use crate::value::unit::Unit;
+use crate::value::Value;
+
+//longest list a range may build
+const MAX_RANGE_LENGTH: i64 = 10_000_000;
 
+#[derive(Default)]
 pub struct Executor {
-    number_stack: Vec<i64>,
-    pub vars: Vec<(String, i64)>,
-    pub token_history: Vec<i64>
+    number_stack: Vec<Value>,
+    pub vars: Environment,
+    pub token_history: Vec<Value>,
+    //i is the imaginary unit and sqrt, ln and ^ extend to negative numbers
+    pub complex: bool,
+    //a list literal of two numbers is the interval between them
+    pub interval: bool,
+    //decimal results are rounded to this many fractional digits and sqrt, exp and ln computed to them
+    pub precision: Option<Precision>,
+    //functions registered by the host application, callable by name like the defined ones
+    pub functions: FunctionRegistry,
+    //moduli of the mod m { ... } blocks being executed, integers are reduced by the innermost one
+    //an exponent inside a block is computed without it, as a plain integer
+    moduli: Vec<Option<i64>>
 }
 
 impl Executor {
     pub fn new() -> Self {
-        Self {number_stack: Vec::new(), vars: Vec::new(), token_history: Vec::new()}
+        Self::default()
     }
 
-    pub fn execute(&mut self, tokens: Vec<Box<dyn TokenTrait>>) -> i64{
-        for (i, token) in tokens.iter().enumerate() {
-            let mut result: i64 = 0;
+    //start with variables already bound, e.g. values provided by the caller
+    pub fn with_vars(vars: impl IntoIterator<Item = (String, Value)>) -> Self {
+        Self {vars: vars.into_iter().collect(), ..Self::default()}
+    }
+
+    //lex, parse and execute a whole input
+    pub fn evaluate(&mut self, input: &str) -> Result<Value, ErrorType> {
+        let mut lexer: Lexer = Lexer::with_functions(input, &self.functions);
+        lexer.process()?;
+
+        let mut st: ShuntingYard = ShuntingYard::new();
+        st.process(lexer.tokens)?;
+        self.execute(st.output_queue)
+    }
+
+    //same as evaluate, with the result of every statement
+    pub fn evaluate_all(&mut self, input: &str) -> Result<Vec<Value>, ErrorType> {
+        let mut lexer: Lexer = Lexer::with_functions(input, &self.functions);
+        lexer.process()?;
+
+        let mut st: ShuntingYard = ShuntingYard::new();
+        st.process(lexer.tokens)?;
+        self.execute_all(st.output_queue)
+    }
+
+    //execute every statement and return the result of the last one
+    pub fn execute(&mut self, tokens: Vec<Box<dyn TokenTrait>>) -> Result<Value, ErrorType> {
+        Ok(self.execute_all(tokens)?.pop().unwrap_or(Value::Integer(0)))
+    }
+
+    pub fn execute_all(&mut self, tokens: Vec<Box<dyn TokenTrait>>) -> Result<Vec<Value>, ErrorType> {
+        self.number_stack.clear();
+        self.token_history.clear();
+        self.moduli.clear();
+        self.vars.close_scopes();
+        Ok(self.run(&tokens)?.into_iter().map(|value| self.rounded(value)).collect())
+    }
+
+    //modulus of the innermost mod block, none outside of blocks and in the exponent of a power
+    fn modulus(&self) -> Option<i64> {
+        self.moduli.last().copied().flatten()
+    }
+
+    //result of a statement at the precision, the operations before it keep guard digits, see Precision::working
+    fn rounded(&self, value: Value) -> Value {
+        let Some(precision) = self.precision else {
+            return value
+        };
+        match value {
+            Value::Decimal(number) => Value::Decimal(number.fit(Some(precision))),
+            Value::Rational(number) => {
+                let quotient = Decimal::from_integer(number.numerator()).quotient(&Decimal::from_integer(number.denominator()), Some(precision));
+                quotient.map_or(Value::Rational(number), Value::Decimal)
+            }
+            Value::Expression(Expr::Number(number)) => match Decimal::from_f64(number) {
+                Some(decimal) => Value::Decimal(decimal.fit(Some(precision))),
+                None => Value::from(Expr::Number(number))
+            },
+            Value::List(values) => Value::List(values.into_iter().map(|value| self.rounded(value)).collect()),
+            value => value
+        }
+    }
+
+    fn run(&mut self, tokens: &[Box<dyn TokenTrait>]) -> Result<Vec<Value>, ErrorType> {
+        let mut results: Vec<Value> = Vec::new();
+        for token in tokens.iter() {
+            let mut result: Value = Value::Integer(0);
             if token.get_token_type() == TokenType::EndOfExpression {
-                if i == tokens.len() - 1 {
-                    return *self.number_stack.first().unwrap();
-                }
-                else {
-                    self.number_stack.clear();
-                    continue;
-                }
+                results.push(self.end_statement()?);
+                continue;
             }
             else if token.get_token_type() == TokenType::Number {
-                result = token.get_value().parse::<i64>().unwrap();
+                result = match token.get_value().parse::<i64>() {
+                    Ok(number) => Value::Integer(number),
+                    //decimal literals are exact, with a precision only the result of the statement is rounded
+                    Err(_) => match (Decimal::parse(&token.get_value()), token.get_value().parse::<f64>()) {
+                        (Some(number), _) => Value::Decimal(number),
+                        (None, Ok(number)) => Value::from(Expr::Number(number)),
+                        (None, Err(_)) => return Error::throw(ErrorType::SyntaxError)
+                    }
+                };
+            }
+            else if token.get_token_type() == TokenType::Text {
+                result = Value::Text(token.get_value());
+            }
+            else if token.get_token_type() == TokenType::Date {
+                result = match Date::parse(&token.get_value()) {
+                    Some(date) => Value::Date(date),
+                    None => return Error::throw(ErrorType::SyntaxError)
+                };
             }
             else {
                 match token.get_token_type() {
@@ -37,65 +146,237 @@ impl Executor {
                             .expect("Failed to downcast TokenOperator");
 
                         if self.number_stack.len() < 2 {
-                            Error::throw::<()>(ErrorType::SyntaxError);
+                            return Error::throw(ErrorType::SyntaxError);
+                        }
+                        let number1 = self.pop_value()?;
+                        let mut number2 = self.pop_value()?;
+                        if self.complex && operator.operator_value == TokenOperatorValue::Power &&
+                            number1.to_number().is_some_and(|exponent| exponent.fract() != 0.0) {
+                            number2 = Executor::complex_domain(number2);
                         }
-                        let number1 = self.number_stack.pop().unwrap();
-                        let number2 = self.number_stack.pop().unwrap();
 
-                        result = operator.execute(number2, number1);
+                        result = self.apply_binary(operator, number2, number1)?;
                     }
                     TokenType::UnaryOperator => {
-                        let number1 = self.number_stack.pop().unwrap();
+                        let number1 = self.pop_value()?;
                         let operator = token.as_any().downcast_ref::<TokenOperator>()
                             .expect("Failed to downcast TokenOperator");
-                        result = operator.execute(number1, 0);
+                        result = Executor::apply_unary(operator, number1)?;
                     }
                     TokenType::Function => {
                         let operator = token.as_any().downcast_ref::<TokenFunction>()
                             .expect("Failed to downcast TokenFunction");
-                        let mut args: VecDeque<i64> = VecDeque::new();
-                        for _ in 0..operator.get_args_count(){
-                            let number = self.number_stack.pop().unwrap();
-                            args.push_front(number)
+                        if operator.is_lazy() {
+                            //symbolic functions read their arguments as expressions, bound names are substituted
+                            let resolve = |name: &str| self.vars.get(name).map(Expr::try_from).transpose();
+                            result = match operator.defined_function() {
+                                Some(DefinedFunction::Solve) => Value::from_values(
+                                    Expr::solve_call(operator, &resolve)?.into_iter().map(Value::from).collect()
+                                ),
+                                Some(DefinedFunction::Sum | DefinedFunction::Prod) => self.iterate(operator)?,
+                                _ => Value::from(Expr::from_tokens(slice::from_ref(token), &resolve)?),
+                            };
+                        }
+                        else {
+                            let mut args: VecDeque<Value> = VecDeque::new();
+                            for _ in 0..operator.get_args_count(){
+                                let number = self.pop_value()?;
+                                args.push_front(number)
+                            }
+                            result = match &operator.function {
+                                Function::Registered(function) => function.call(&Vec::from(args))?,
+                                Function::Defined(function) => {
+                                    if self.complex && matches!(function, DefinedFunction::Sqrt | DefinedFunction::Ln) {
+                                        args = args.into_iter().map(Executor::complex_domain).collect();
+                                    }
+                                    self.apply_function(*function, args.into())?
+                                }
+                            };
                         }
-                        let args = args.into_iter().collect::<Vec<i64>>();
-                        result = operator.execute(args);
                     }
                     TokenType::Name => {
-                        if let Some(operator) = tokens.get(i + 1) {
-                            if operator.get_token_type() == TokenType::BinaryOperator {
-                                Error::throw::<()>(ErrorType::SyntaxError);
-                            }
+                        //assignment leaves its value on the stack so it can be chained or used in an expression
+                        let value = match self.number_stack.last() {
+                            Some(value) => value.clone(),
+                            None => return Error::throw(ErrorType::SyntaxError)
+                        };
+                        self.vars.assign(&token.get_value(), value);
+                        continue;
+                    }
+                    TokenType::Variable => {
+                        result = match self.vars.get(&token.get_value()) {
+                            Some(value) => value,
+                            None if self.complex && token.get_value() == "i" => Value::Complex(Complex::I),
+                            None if token.get_value() == "now" => Value::Date(Date::now()),
+                            None if token.get_value() == "true" => Value::Bool(true),
+                            None if token.get_value() == "false" => Value::Bool(false),
+                            None => return Error::throw(ErrorType::UnboundVariable)
+                        };
+                    }
+                    TokenType::Unit => {
+                        result = match Unit::parse(&token.get_value()) {
+                            Some(unit) => Value::Quantity(Quantity::new(1.0, unit)),
+                            None => return Error::throw(ErrorType::UnboundVariable)
+                        };
+                    }
+                    TokenType::List => {
+                        let count = Executor::token_count(token.as_ref())?;
+                        let mut values: VecDeque<Value> = VecDeque::new();
+                        for _ in 0..count {
+                            values.push_front(self.pop_value()?);
                         }
-                        if let Some(value) = self.number_stack.last() {
-                            if let Some(index) = self.vars.iter().position(|(name, _)| name == &token.get_value()) {
-                                self.vars.get_mut(index).unwrap().1 = *value;
+                        let values: Vec<Value> = values.into();
+                        result = match (self.interval, values.iter().map(Value::to_number).collect::<Vec<_>>().as_slice()) {
+                            (true, [Some(lo), Some(hi)]) => Value::Interval(Interval::new(*lo, *hi)?),
+                            _ => Value::from_elements(values)?
+                        };
+                    }
+                    TokenType::Range => {
+                        let to = self.pop_value()?;
+                        let from = self.pop_value()?;
+                        result = match (from, to) {
+                            (Value::Integer(from), Value::Integer(to)) if to.saturating_sub(from) < MAX_RANGE_LENGTH => {
+                                Value::List((from..=to).map(Value::Integer).collect())
                             }
-                            else { self.vars.push((token.get_value(), *value)); }
+                            _ => return Error::throw(ErrorType::InvalidOperation)
+                        };
+                    }
+                    TokenType::PlusMinus => {
+                        let radius = self.pop_value()?;
+                        let center = self.pop_value()?;
+                        result = match (center.to_number(), radius.to_number()) {
+                            (Some(center), Some(radius)) => Value::Interval(Interval::around(center, radius)?),
+                            _ => return Error::throw(ErrorType::InvalidOperation)
+                        };
+                    }
+                    TokenType::Comparison => {
+                        let right = self.pop_value()?;
+                        let left = self.pop_value()?;
+                        result = Value::Bool(Executor::compare(&token.get_value(), left, right)?);
+                    }
+                    TokenType::Convert => {
+                        let unit = self.pop_value()?;
+                        let value = self.pop_value()?;
+                        result = Executor::convert(value, unit)?;
+                    }
+                    TokenType::Index => {
+                        let count = Executor::token_count(token.as_ref())?;
+                        let mut indexes: VecDeque<Value> = VecDeque::new();
+                        for _ in 0..count {
+                            indexes.push_front(self.pop_value()?);
                         }
+                        let value = self.pop_value()?;
+                        result = Executor::index(value, indexes.into())?;
+                    }
+                    TokenType::Let => {
+                        self.vars.push_scope();
                         continue;
                     }
-                    TokenType::Variable => {
-                        if self.vars.iter().any(|(name, _)| name == &token.get_value()) {
-                            result = self.vars.
-                                iter().
-                                find(|(name, _)| name == &token.get_value()).
-                                unwrap().1;
+                    TokenType::Binding => {
+                        let value = self.pop_value()?;
+                        self.vars.define(&token.get_value(), value);
+                        continue;
+                    }
+                    TokenType::In => {
+                        self.vars.pop_scope();
+                        continue;
+                    }
+                    TokenType::LeftBrace => {
+                        match self.pop_value()? {
+                            Value::Integer(modulus) if modulus > 0 => self.moduli.push(Some(modulus)),
+                            _ => return Error::throw(ErrorType::InvalidOperation)
                         }
-                        else { Error::throw::<()>(ErrorType::UnboundVariable); }
+                        continue;
+                    }
+                    TokenType::RightBrace => {
+                        let modulus = self.moduli.pop().flatten();
+                        result = match (self.pop_value()?, modulus) {
+                            (Value::Integer(number), Some(modulus)) => Value::Integer(Math::mod_reduce(number as i128, modulus)),
+                            (_, Some(_)) => return Error::throw(ErrorType::InvalidOperation),
+                            (value, None) => value
+                        };
+                    }
+                    TokenType::Exponent => {
+                        self.moduli.push(None);
+                        continue;
+                    }
+                    TokenType::ExponentEnd => {
+                        self.moduli.pop();
+                        continue;
                     }
                     _ => {}
                 }
             }
 
-            self.token_history.push(result);
+            self.token_history.push(result.clone());
             self.number_stack.push(result);
         }
-        if self.number_stack.len() > 1 { Error::throw::<()>(ErrorType::SyntaxError); }
+        if !self.number_stack.is_empty() {
+            results.push(self.end_statement()?);
+        }
+        Ok(results)
+    }
+
+    //number of elements of a list or index token
+    fn token_count(token: &dyn TokenTrait) -> Result<usize, ErrorType> {
+        match token.get_value().parse::<usize>() {
+            Ok(count) => Ok(count),
+            Err(_) => Error::throw(ErrorType::SyntaxError)
+        }
+    }
+
+    //sum(i, from, to, body) and prod(i, from, to, body), the body is executed once per index
+    //the index lives in its own scope so it never leaks into the variables
+    fn iterate(&mut self, function: &TokenFunction) -> Result<Value, ErrorType> {
+        let [index, from, to, body] = function.arguments.as_slice() else {
+            return Error::throw(ErrorType::InvalidOperation)
+        };
+        let index = Expr::variable_name(index)?;
+        let (from, to) = match (self.evaluate_argument(from)?, self.evaluate_argument(to)?) {
+            (Value::Integer(from), Value::Integer(to)) => (from, to),
+            _ => return Error::throw(ErrorType::InvalidOperation)
+        };
+        let (operator, mut result) = match function.defined_function() {
+            Some(DefinedFunction::Sum) => ("+", Value::Integer(0)),
+            _ => ("*", Value::Integer(1)),
+        };
+        let operator = TokenOperator::new(TokenType::BinaryOperator, operator.to_string());
+
+        self.vars.push_scope();
+        let run = || {
+            for i in from..=to {
+                self.vars.define(&index, Value::Integer(i));
+                let term = self.evaluate_argument(body)?;
+                result = self.apply_binary(&operator, result.clone(), term)?;
+            }
+            Ok(result)
+        };
+        let result = run();
+        self.vars.pop_scope();
+        result
+    }
+
+    //execute an unevaluated argument on its own stack
+    fn evaluate_argument(&mut self, tokens: &[Box<dyn TokenTrait>]) -> Result<Value, ErrorType> {
+        let outer_stack = std::mem::take(&mut self.number_stack);
+        let result = self.run(tokens);
+        self.number_stack = outer_stack;
+        match result?.pop() {
+            Some(value) => Ok(value),
+            None => Error::throw(ErrorType::SyntaxError)
+        }
+    }
+
+    fn end_statement(&mut self) -> Result<Value, ErrorType> {
+        if self.number_stack.len() > 1 { return Error::throw(ErrorType::SyntaxError); }
+
+        Ok(self.number_stack.pop().unwrap_or(Value::Integer(0)))
+    }
 
+    fn pop_value(&mut self) -> Result<Value, ErrorType> {
         match self.number_stack.pop() {
-            Some(number) => number,
-            None => 0
+            Some(value) => Ok(value),
+            None => Error::throw(ErrorType::SyntaxError)
         }
     }
 }
@@ -0,0 +1,663 @@
+use std::cmp::Ordering;
+use crate::errors::{Error, ErrorType};
+use crate::executor::Executor;
+use crate::lexer::tokens::binary_operator_token::{TokenOperator, TokenOperatorValue};
+use crate::lexer::tokens::function_token::DefinedFunction;
+use crate::maths::Math;
+use crate::symbolic::Expr;
+use crate::value::complex::Complex;
+use crate::value::date::Date;
+use crate::value::decimal::{Decimal, Precision, Rounding};
+use crate::value::interval::Interval;
+use crate::value::matrix::Matrix;
+use crate::value::quantity::Quantity;
+use crate::value::unit::Unit;
+use crate::value::Value;
+
+//largest exponent of a decimal power, past it the power is a floating point number
+const MAX_DECIMAL_EXPONENT: u64 = 10_000;
+
+impl Executor {
+    //operators apply element-wise to lists, a value on the other side goes with every element
+    pub(super) fn apply_binary(&self, operator: &TokenOperator, left: Value, right: Value) -> Result<Value, ErrorType> {
+        Ok(match (left, right) {
+            (left @ Value::Matrix(_), right) | (left, right @ Value::Matrix(_)) => Executor::apply_matrix(operator, left, right)?,
+            (Value::List(left), Value::List(right)) => {
+                if left.len() != right.len() {
+                    return Error::throw(ErrorType::ShapeMismatch);
+                }
+                Value::List(left.into_iter().zip(right)
+                    .map(|(left, right)| self.apply_binary(operator, left, right))
+                    .collect::<Result<_, _>>()?)
+            }
+            (Value::List(left), right) => Value::List(left.into_iter()
+                .map(|left| self.apply_binary(operator, left, right.clone()))
+                .collect::<Result<_, _>>()?),
+            (left, Value::List(right)) => Value::List(right.into_iter()
+                .map(|right| self.apply_binary(operator, left.clone(), right))
+                .collect::<Result<_, _>>()?),
+            (Value::Text(left), Value::Text(right)) if operator.operator_value == TokenOperatorValue::Plus => Value::Text(left + &right),
+            (Value::Text(_) | Value::Bool(_), _) | (_, Value::Text(_) | Value::Bool(_)) => return Error::throw(ErrorType::TypeError),
+            //a mod block works on integers only
+            (Value::Integer(left), Value::Integer(right)) if self.modulus().is_some() => {
+                Value::Integer(Executor::apply_modular(operator, left, right, self.modulus().unwrap())?)
+            }
+            _ if self.modulus().is_some() => return Error::throw(ErrorType::InvalidOperation),
+            (left @ Value::Date(_), right) | (left, right @ Value::Date(_)) => Executor::apply_date_binary(operator, left, right)?,
+            (left @ Value::Quantity(_), right) | (left, right @ Value::Quantity(_)) => Executor::apply_quantity_binary(operator, left, right)?,
+            (left @ Value::Complex(_), right) | (left, right @ Value::Complex(_)) => Executor::apply_complex_binary(operator, left, right)?,
+            (left @ Value::Interval(_), right) | (left, right @ Value::Interval(_)) => Executor::apply_interval_binary(operator, left, right)?,
+            //a decimal is an exact fraction, with a fraction the result stays one
+            (left @ Value::Rational(_), right @ Value::Decimal(_)) | (left @ Value::Decimal(_), right @ Value::Rational(_)) => {
+                Executor::apply_rational_binary(operator, left, right)?
+            }
+            (left @ Value::Decimal(_), right) | (left, right @ Value::Decimal(_)) => self.apply_decimal_binary(operator, left, right)?,
+            //with a precision quotients of integers are decimals instead of being truncated
+            (left @ Value::Integer(_), right @ Value::Integer(number)) if self.precision.is_some() &&
+                matches!((operator.operator_value, number), (TokenOperatorValue::Divide, _) | (TokenOperatorValue::Power, ..=-1)) => {
+                self.apply_decimal_binary(operator, left, right)?
+            }
+            (left @ Value::Rational(_), right) | (left, right @ Value::Rational(_)) => Executor::apply_rational_binary(operator, left, right)?,
+            (Value::Integer(left), Value::Integer(right)) => operator.execute(left, right)?,
+            (left, right) => Executor::apply_float_binary(operator, left, right)?
+        })
+    }
+
+    //floating point numbers, or a symbolic expression when a side is one
+    //a result which is no real number, like a fractional power of a negative number, is an InvalidOperation
+    fn apply_float_binary(operator: &TokenOperator, left: Value, right: Value) -> Result<Value, ErrorType> {
+        let (left, right) = (Expr::try_from(left)?, Expr::try_from(right)?);
+        if matches!(operator.operator_value, TokenOperatorValue::Divide | TokenOperatorValue::Modulo) && right == Expr::Number(0.0) {
+            return Error::throw(ErrorType::InvalidOperation);
+        }
+        match Expr::binary(operator.operator_value, left, right) {
+            Expr::Number(number) if !number.is_finite() => Error::throw(ErrorType::InvalidOperation),
+            result => Ok(Value::from(result))
+        }
+    }
+
+    //inside mod m { ... } every result is reduced modulo m
+    //a quotient is a product by the inverse of the divisor and % is a modulo which is never negative
+    fn apply_modular(operator: &TokenOperator, left: i64, right: i64, modulus: i64) -> Result<i64, ErrorType> {
+        Ok(match operator.operator_value {
+            TokenOperatorValue::Plus => Math::mod_reduce(left as i128 + right as i128, modulus),
+            TokenOperatorValue::Minus => Math::mod_reduce(left as i128 - right as i128, modulus),
+            TokenOperatorValue::Multiply => Math::mod_mul(left, right, modulus),
+            TokenOperatorValue::Divide => Math::mod_mul(left, Math::mod_inverse(right, modulus)?, modulus),
+            TokenOperatorValue::Power => Math::mod_pow(left, right, modulus)?,
+            TokenOperatorValue::Modulo if right == 0 => return Error::throw(ErrorType::InvalidOperation),
+            TokenOperatorValue::Modulo => Math::mod_reduce(left.rem_euclid(right) as i128, modulus),
+        })
+    }
+
+    //sums need operands of the same dimension, a plain number has none
+    fn apply_quantity_binary(operator: &TokenOperator, left: Value, right: Value) -> Result<Value, ErrorType> {
+        let (Some(left), Some(right)) = (left.to_quantity(), right.to_quantity()) else {
+            return Error::throw(ErrorType::InvalidOperation)
+        };
+        Ok(Value::from_quantity(match operator.operator_value {
+            TokenOperatorValue::Plus => left.try_add(right)?,
+            TokenOperatorValue::Minus => left.try_sub(right)?,
+            TokenOperatorValue::Multiply => left * right,
+            TokenOperatorValue::Divide => left.try_div(right)?,
+            TokenOperatorValue::Modulo => left.try_rem(right)?,
+            TokenOperatorValue::Power => match (right.unit().is_dimensionless(), right.magnitude()) {
+                (true, exponent) if exponent.fract() == 0.0 && exponent.abs() <= i32::MAX as f64 => left.powi(exponent as i32),
+                (true, _) => return Error::throw(ErrorType::InvalidOperation),
+                (false, _) => return Error::throw(ErrorType::DimensionMismatch),
+            },
+        }))
+    }
+
+    //a date moves by a duration and the difference of two dates is a duration in days
+    fn apply_date_binary(operator: &TokenOperator, left: Value, right: Value) -> Result<Value, ErrorType> {
+        let seconds = |duration: &Quantity| if duration.unit().is_time() {
+            Ok(duration.magnitude())
+        }
+        else {
+            Error::throw(ErrorType::DimensionMismatch)
+        };
+        match (operator.operator_value, left, right) {
+            (TokenOperatorValue::Plus, Value::Date(date), Value::Quantity(duration)) |
+            (TokenOperatorValue::Plus, Value::Quantity(duration), Value::Date(date)) => Ok(Value::Date(date.add_seconds(seconds(&duration)?)?)),
+            (TokenOperatorValue::Minus, Value::Date(date), Value::Quantity(duration)) => Ok(Value::Date(date.add_seconds(-seconds(&duration)?)?)),
+            (TokenOperatorValue::Minus, Value::Date(later), Value::Date(earlier)) => {
+                let days = later.seconds_since(&earlier) / Unit::lookup("day").unwrap().factor();
+                let unit = Unit::lookup(if days.abs() == 1.0 { "day" } else { "days" }).unwrap();
+                Ok(Value::Quantity(Quantity::new(days, unit)))
+            }
+            (TokenOperatorValue::Plus | TokenOperatorValue::Minus, _, _) => Error::throw(ErrorType::DimensionMismatch),
+            _ => Error::throw(ErrorType::InvalidOperation)
+        }
+    }
+
+    //left ==, !=, <, <=, > or >= right, numbers are compared exactly when they can be
+    //a floating point NaN is neither less, equal nor greater than anything
+    pub(super) fn compare(comparison: &str, left: Value, right: Value) -> Result<bool, ErrorType> {
+        let ordering = match (&left, &right) {
+            (Value::Bool(a), Value::Bool(b)) if matches!(comparison, "==" | "!=") => Some(a.cmp(b)),
+            (Value::Text(a), Value::Text(b)) => Some(a.cmp(b)),
+            (Value::Date(a), Value::Date(b)) => Some(a.cmp(b)),
+            //a/b against a decimal d is a against b*d, both exact whatever their size
+            (Value::Rational(a), Value::Decimal(b)) => Some(Decimal::from_integer(a.numerator()).compare(&b.mul(&Decimal::from_integer(a.denominator())))),
+            (Value::Decimal(a), Value::Rational(b)) => Some(a.mul(&Decimal::from_integer(b.denominator())).compare(&Decimal::from_integer(b.numerator()))),
+            (Value::Quantity(_), _) | (_, Value::Quantity(_)) => match (left.to_quantity(), right.to_quantity()) {
+                (Some(a), Some(b)) => a.convert(b.unit())?.magnitude().partial_cmp(&b.magnitude()),
+                _ => return Error::throw(ErrorType::TypeError)
+            },
+            _ => if let (Some(a), Some(b)) = (left.to_rational(), right.to_rational()) {
+                Some(a.cmp(&b))
+            }
+            else if let (Some(a), Some(b)) = (left.to_decimal(), right.to_decimal()) {
+                Some(a.compare(&b))
+            }
+            else if let (Some(a), Some(b)) = (left.to_number(), right.to_number()) {
+                a.partial_cmp(&b)
+            }
+            else {
+                return Error::throw(ErrorType::TypeError)
+            }
+        };
+        Ok(match comparison {
+            "==" => ordering == Some(Ordering::Equal),
+            "!=" => ordering != Some(Ordering::Equal),
+            "<" => ordering == Some(Ordering::Less),
+            "<=" => matches!(ordering, Some(Ordering::Less | Ordering::Equal)),
+            ">" => ordering == Some(Ordering::Greater),
+            _ => matches!(ordering, Some(Ordering::Greater | Ordering::Equal)),
+        })
+    }
+
+    //value to unit, lists are converted element by element
+    pub(super) fn convert(value: Value, unit: Value) -> Result<Value, ErrorType> {
+        let Value::Quantity(unit) = unit else {
+            return Error::throw(ErrorType::DimensionMismatch)
+        };
+        match value {
+            Value::List(values) => Ok(Value::List(values.into_iter()
+                .map(|value| Executor::convert(value, Value::Quantity(unit.clone())))
+                .collect::<Result<_, _>>()?)),
+            value => match value.to_quantity() {
+                Some(quantity) => Ok(Value::Quantity(quantity.convert(unit.unit())?)),
+                None => Error::throw(ErrorType::InvalidOperation)
+            }
+        }
+    }
+
+    //decimals with integers stay exact, with a floating point number they are one
+    //with a precision the quotient of two integers is one too, and results keep guard digits until the statement's is rounded
+    fn apply_decimal_binary(&self, operator: &TokenOperator, left: Value, right: Value) -> Result<Value, ErrorType> {
+        let working = self.precision.map(Precision::working);
+        let positive = |number: &Decimal| number.compare(&Decimal::from_integer(0)) == Ordering::Greater;
+        let result = match (left.to_decimal(), right.to_decimal()) {
+            (Some(a), Some(b)) => match (operator.operator_value, b.to_integer()) {
+                (TokenOperatorValue::Plus, _) => Some(a.add(&b).fit(working)),
+                (TokenOperatorValue::Minus, _) => Some(a.sub(&b).fit(working)),
+                (TokenOperatorValue::Multiply, _) => Some(a.mul(&b).fit(working)),
+                (TokenOperatorValue::Divide, _) => Some(a.quotient(&b, working)?),
+                (TokenOperatorValue::Modulo, _) => Some(a.rem(&b)?.fit(working)),
+                (TokenOperatorValue::Power, Some(exponent)) if exponent.unsigned_abs() <= MAX_DECIMAL_EXPONENT => {
+                    Some(a.power(exponent, working)?)
+                }
+                //a fractional power of a positive number is computed to the precision rather than in floating point
+                (TokenOperatorValue::Power, _) => match working {
+                    Some(working) if positive(&a) => Some(a.power_real(&b, working.digits, working.rounding)?),
+                    _ => None
+                },
+            },
+            _ => None
+        };
+        match result {
+            Some(number) => Ok(Value::Decimal(number)),
+            None => Executor::apply_float_binary(operator, left, right)
+        }
+    }
+
+    fn apply_interval_binary(operator: &TokenOperator, left: Value, right: Value) -> Result<Value, ErrorType> {
+        let (Some(left), Some(right)) = (left.to_interval(), right.to_interval()) else {
+            return Error::throw(ErrorType::InvalidOperation)
+        };
+        Ok(Value::Interval(match operator.operator_value {
+            TokenOperatorValue::Plus => left.try_add(right)?,
+            TokenOperatorValue::Minus => left.try_sub(right)?,
+            TokenOperatorValue::Multiply => left.try_mul(right)?,
+            TokenOperatorValue::Divide => left.try_div(right)?,
+            TokenOperatorValue::Power => left.pow(&right)?,
+            TokenOperatorValue::Modulo => left.rem(&right)?,
+        }))
+    }
+
+    //fractions with integers stay exact, a result past the i64 range or an operation with a floating point number is one
+    fn apply_rational_binary(operator: &TokenOperator, left: Value, right: Value) -> Result<Value, ErrorType> {
+        let result = match (left.to_rational(), right.to_rational()) {
+            (Some(a), Some(b)) => match (operator.operator_value, b.is_integer()) {
+                (TokenOperatorValue::Divide | TokenOperatorValue::Modulo, _) if b.numerator() == 0 => {
+                    return Error::throw(ErrorType::InvalidOperation)
+                }
+                (TokenOperatorValue::Power, true) if a.numerator() == 0 && b.numerator() < 0 => {
+                    return Error::throw(ErrorType::InvalidOperation)
+                }
+                (TokenOperatorValue::Plus, _) => a.checked_add(&b),
+                (TokenOperatorValue::Minus, _) => a.checked_sub(&b),
+                (TokenOperatorValue::Multiply, _) => a.checked_mul(&b),
+                (TokenOperatorValue::Divide, _) => a.checked_div(&b),
+                (TokenOperatorValue::Modulo, _) => a.checked_rem(&b),
+                (TokenOperatorValue::Power, true) => a.checked_powi(b.numerator()),
+                (TokenOperatorValue::Power, false) => None,
+            },
+            _ => None
+        };
+        match result {
+            Some(number) => Ok(Value::from_rational(number)),
+            None => Executor::apply_float_binary(operator, left, right)
+        }
+    }
+
+    fn apply_complex_binary(operator: &TokenOperator, left: Value, right: Value) -> Result<Value, ErrorType> {
+        let (Some(left), Some(right)) = (left.to_complex(), right.to_complex()) else {
+            return Error::throw(ErrorType::InvalidOperation)
+        };
+        Ok(Value::from_complex(match operator.operator_value {
+            TokenOperatorValue::Plus => left + right,
+            TokenOperatorValue::Minus => left - right,
+            TokenOperatorValue::Multiply => left * right,
+            TokenOperatorValue::Divide => left.try_div(&right)?,
+            TokenOperatorValue::Power => left.pow(right)?,
+            TokenOperatorValue::Modulo => return Error::throw(ErrorType::InvalidOperation),
+        }))
+    }
+
+    //in complex mode the negative numbers given to sqrt, ln or a fractional power are complex numbers
+    pub(super) fn complex_domain(value: Value) -> Value {
+        match value {
+            Value::List(values) => Value::List(values.into_iter().map(Executor::complex_domain).collect()),
+            value => match value.to_number() {
+                Some(number) if number < 0.0 => Value::Complex(Complex::new(number, 0.0)),
+                _ => value
+            }
+        }
+    }
+
+    //* is the matrix product, a list is a column vector on its right and a row vector on its left
+    //^ is a power of a square matrix, other operators with a number apply to every element
+    fn apply_matrix(operator: &TokenOperator, left: Value, right: Value) -> Result<Value, ErrorType> {
+        let elementwise = |a: f64, b: f64| match Executor::apply_float_binary(operator, Value::from_number(a), Value::from_number(b))?.to_number() {
+            Some(number) => Ok(number),
+            None => Error::throw(ErrorType::InvalidOperation)
+        };
+        Ok(match (operator.operator_value, left, right) {
+            (TokenOperatorValue::Multiply, Value::Matrix(left), Value::Matrix(right)) => Value::Matrix(left.product(&right)?),
+            (TokenOperatorValue::Multiply, Value::Matrix(left), vector @ Value::List(_)) => {
+                let vector = Executor::numbers(&vector)?;
+                Value::List(left.product(&Matrix::new(vector.len(), 1, vector))?.to_values())
+            }
+            (TokenOperatorValue::Multiply, vector @ Value::List(_), Value::Matrix(right)) => {
+                let vector = Executor::numbers(&vector)?;
+                Value::List(Matrix::new(1, vector.len(), vector).product(&right)?.to_values())
+            }
+            (TokenOperatorValue::Plus | TokenOperatorValue::Minus, Value::Matrix(left), Value::Matrix(right)) => {
+                Value::Matrix(left.zip_with(&right, elementwise)?)
+            }
+            (TokenOperatorValue::Power, Value::Matrix(matrix), Value::Integer(exponent)) => Value::Matrix(matrix.power(exponent)?),
+            (TokenOperatorValue::Power, ..) => return Error::throw(ErrorType::InvalidOperation),
+            (_, Value::Matrix(matrix), number) if number.to_number().is_some() => {
+                let number = Executor::number(&number)?;
+                Value::Matrix(matrix.map(|element| elementwise(element, number))?)
+            }
+            (_, number, Value::Matrix(matrix)) if number.to_number().is_some() => {
+                let number = Executor::number(&number)?;
+                Value::Matrix(matrix.map(|element| elementwise(number, element))?)
+            }
+            _ => return Error::throw(ErrorType::InvalidOperation)
+        })
+    }
+
+    pub(super) fn apply_unary(operator: &TokenOperator, value: Value) -> Result<Value, ErrorType> {
+        Ok(match value {
+            Value::List(values) => Value::List(values.into_iter()
+                .map(|value| Executor::apply_unary(operator, value))
+                .collect::<Result<_, _>>()?),
+            Value::Matrix(matrix) if operator.operator_value == TokenOperatorValue::Minus => Value::Matrix(matrix.map(|element| Ok(-element))?),
+            Value::Complex(number) if operator.operator_value == TokenOperatorValue::Minus => Value::Complex(-number),
+            Value::Interval(interval) if operator.operator_value == TokenOperatorValue::Minus => Value::Interval(-interval),
+            Value::Quantity(quantity) if operator.operator_value == TokenOperatorValue::Minus => Value::Quantity(-quantity),
+            Value::Decimal(number) if operator.operator_value == TokenOperatorValue::Minus => Value::Decimal(-number),
+            Value::Text(_) => return Error::throw(ErrorType::TypeError),
+            Value::Bool(_) => return Error::throw(ErrorType::TypeError),
+            Value::Rational(number) if operator.operator_value == TokenOperatorValue::Minus => match number.checked_neg() {
+                Some(number) => Value::Rational(number),
+                None => Value::from_number(-number.to_f64())
+            },
+            Value::Integer(number) => operator.execute(number, 0)?,
+            Value::Expression(expr) if operator.operator_value == TokenOperatorValue::Minus => Value::from(-expr),
+            expression => expression
+        })
+    }
+
+    //functions of one argument apply to each element of a list or a matrix
+    pub(super) fn apply_function(&self, function: DefinedFunction, args: Vec<Value>) -> Result<Value, ErrorType> {
+        if matches!(function, DefinedFunction::Str | DefinedFunction::Format) ||
+            args.iter().any(|arg| matches!(arg, Value::Text(_) | Value::Bool(_))) {
+            return Executor::apply_text(function, &args);
+        }
+        if function.is_statistic() {
+            return Executor::apply_statistic(function, &args);
+        }
+        if function.is_linear_algebra() {
+            return Executor::apply_linear_algebra(function, &args);
+        }
+        if function.is_financial() {
+            return self.apply_financial(function, &args);
+        }
+        //min and max of lists are taken over all their elements
+        if matches!(function, DefinedFunction::Max | DefinedFunction::Min) &&
+            args.iter().any(|arg| matches!(arg, Value::List(_))) {
+            let values = args.into_iter().flat_map(|arg| match arg {
+                Value::List(values) => values,
+                value => vec![value],
+            }).collect();
+            return self.apply_function(function, values);
+        }
+        match (function, args.as_slice()) {
+            (DefinedFunction::Len, [Value::List(values)]) => return Ok(Value::Integer(values.len() as i64)),
+            (DefinedFunction::Len, [Value::Matrix(matrix)]) => return Ok(Value::Integer(matrix.rows() as i64)),
+            (DefinedFunction::Len, [_]) => return Error::throw(ErrorType::TypeError),
+            (DefinedFunction::Len, _) => return Error::throw(ErrorType::InvalidOperation),
+            (DefinedFunction::Hex, [Value::Integer(number)]) => return Ok(Value::Text(Value::hex(*number))),
+            (DefinedFunction::Bin, [Value::Integer(number)]) => return Ok(Value::Text(Value::bin(*number))),
+            (_, [Value::List(values)]) => {
+                return Ok(Value::List(values.iter()
+                    .map(|value| self.apply_function(function, vec![value.clone()]))
+                    .collect::<Result<_, _>>()?));
+            }
+            (_, [Value::Matrix(matrix)]) => {
+                return Value::from_elements(matrix.to_rows().into_iter()
+                    .map(|row| self.apply_function(function, vec![row]))
+                    .collect::<Result<_, _>>()?);
+            }
+            (DefinedFunction::Hex | DefinedFunction::Bin, _) => return Error::throw(ErrorType::TypeError),
+            //the same number as a floating point one, the way a session writes them back
+            (DefinedFunction::Float, [value]) => return match value.to_number() {
+                Some(number) => Ok(Value::from(Expr::Number(number))),
+                None => Error::throw(ErrorType::TypeError)
+            },
+            //a complex number of its parts in any mode, also the way a session writes them back
+            (DefinedFunction::Complex, [re, im]) => return match (re.to_number(), im.to_number()) {
+                (Some(re), Some(0.0)) => Ok(Value::from(Expr::Number(re))),
+                (Some(re), Some(im)) => Ok(Value::Complex(Complex::new(re, im))),
+                _ => Error::throw(ErrorType::TypeError)
+            },
+            _ => {}
+        }
+        //with a precision sqrt, exp and ln of exact numbers are computed to it
+        if let (Some(precision), [value]) = (self.precision.map(Precision::working), args.as_slice()) {
+            if let (Some(number), DefinedFunction::Sqrt | DefinedFunction::Exp | DefinedFunction::Ln) = (value.to_decimal(), function) {
+                return Ok(Value::Decimal(match function {
+                    DefinedFunction::Sqrt => number.sqrt(precision.digits, precision.rounding)?,
+                    DefinedFunction::Exp => number.exp(precision.digits, precision.rounding)?,
+                    _ => number.ln(precision.digits, precision.rounding)?,
+                }));
+            }
+        }
+        match (function, args.as_slice()) {
+            (DefinedFunction::Sqrt, [Value::Quantity(quantity)]) => return Ok(Value::from_quantity(quantity.sqrt()?)),
+            (DefinedFunction::Abs, [Value::Quantity(quantity)]) => return Ok(Value::Quantity(quantity.abs())),
+            (DefinedFunction::Abs, [Value::Decimal(number)]) => return Ok(Value::Decimal(number.abs())),
+            (DefinedFunction::Round, [value, Value::Integer(digits)]) => return self.round(value, *digits),
+            (_, [Value::Quantity(_), ..]) => return Error::throw(ErrorType::DimensionMismatch),
+            _ => {}
+        }
+        if function.is_calendar() || args.iter().any(|arg| matches!(arg, Value::Date(_))) {
+            return Executor::apply_date(function, &args);
+        }
+        if args.iter().any(|arg| matches!(arg, Value::Interval(_))) {
+            return Executor::apply_interval(function, &args);
+        }
+        let complex = args.iter().any(|arg| matches!(arg, Value::Complex(_)));
+        if (complex || function.is_complex_part()) && args.iter().all(|arg| arg.to_complex().is_some()) {
+            return Executor::apply_complex(function, &args);
+        }
+        match args.iter().map(|arg| match arg {
+            Value::Integer(number) => Some(*number),
+            _ => None
+        }).collect::<Option<Vec<i64>>>() {
+            Some(args) => match function.execute(args)? {
+                Value::Expression(Expr::Number(number)) if !number.is_finite() => Error::throw(ErrorType::InvalidOperation),
+                value => Ok(value)
+            },
+            None => {
+                let call = Expr::Call(function, args.into_iter().map(Expr::try_from).collect::<Result<_, _>>()?);
+                //with decimal arguments the function is computed in floating point
+                match &call {
+                    Expr::Call(_, args) if args.iter().all(|arg| matches!(arg, Expr::Number(_))) => {
+                        match call.constant()? {
+                            number if number.is_finite() => Ok(Value::from(Expr::Number(number))),
+                            _ => Error::throw(ErrorType::InvalidOperation)
+                        }
+                    }
+                    _ => Ok(Value::from(call))
+                }
+            }
+        }
+    }
+
+    fn apply_complex(function: DefinedFunction, args: &[Value]) -> Result<Value, ErrorType> {
+        let [Some(z)] = args.iter().map(Value::to_complex).collect::<Vec<_>>()[..] else {
+            return Error::throw(ErrorType::InvalidOperation)
+        };
+        Ok(match function {
+            DefinedFunction::Re => Value::from_number(z.re),
+            DefinedFunction::Im => Value::from_number(z.im),
+            DefinedFunction::Arg => Value::from_number(z.arg()),
+            DefinedFunction::Abs => Value::from_number(z.abs()),
+            DefinedFunction::Conj => Value::from_complex(z.conj()),
+            DefinedFunction::Sqrt => Value::from_complex(z.sqrt()),
+            DefinedFunction::Exp => Value::from_complex(z.exp()),
+            DefinedFunction::Ln => Value::from_complex(z.ln()?),
+            DefinedFunction::Sin => Value::from_complex(z.sin()),
+            DefinedFunction::Cos => Value::from_complex(z.cos()),
+            _ => return Error::throw(ErrorType::InvalidOperation)
+        })
+    }
+
+    //a text or a bool given to any other function is a type error
+    fn apply_text(function: DefinedFunction, args: &[Value]) -> Result<Value, ErrorType> {
+        match (function, args) {
+            (DefinedFunction::Str, [value]) => Ok(Value::Text(value.to_string())),
+            (DefinedFunction::Format, [Value::Text(template), values @ ..]) => Ok(Value::Text(Value::format(template, values)?)),
+            (DefinedFunction::Len, [Value::Text(text)]) => Ok(Value::Integer(text.chars().count() as i64)),
+            _ => Error::throw(ErrorType::TypeError)
+        }
+    }
+
+    //dates are compared by max and min
+    fn apply_date(function: DefinedFunction, args: &[Value]) -> Result<Value, ErrorType> {
+        let dates = args.iter().map(|arg| match arg {
+            Value::Date(date) => Ok(*date),
+            _ => Error::throw(ErrorType::InvalidOperation)
+        }).collect::<Result<Vec<Date>, _>>()?;
+        match (function, dates.as_slice()) {
+            (DefinedFunction::Weekday, [date]) => Ok(Value::Integer(date.weekday())),
+            (DefinedFunction::IsoWeek, [date]) => Ok(Value::Integer(date.iso_week())),
+            (DefinedFunction::Max, dates) if !dates.is_empty() => Ok(Value::Date(*dates.iter().max().unwrap())),
+            (DefinedFunction::Min, dates) if !dates.is_empty() => Ok(Value::Date(*dates.iter().min().unwrap())),
+            _ => Error::throw(ErrorType::InvalidOperation)
+        }
+    }
+
+    //only functions which are monotone on their domain take intervals, the image is then the interval of the images
+    fn apply_interval(function: DefinedFunction, args: &[Value]) -> Result<Value, ErrorType> {
+        let intervals = args.iter().map(|arg| arg.to_interval().ok_or(ErrorType::InvalidOperation))
+            .collect::<Result<Vec<Interval>, _>>()?;
+        Ok(Value::Interval(match (function, intervals.as_slice()) {
+            (DefinedFunction::Sqrt, [x]) => x.sqrt()?,
+            (DefinedFunction::Exp, [x]) => x.map_increasing(f64::exp)?,
+            (DefinedFunction::Ln, [x]) => x.map_increasing(f64::ln)?,
+            (DefinedFunction::Abs, [x]) => x.abs(),
+            (DefinedFunction::Max, [x, y]) => x.max(y),
+            (DefinedFunction::Min, [x, y]) => x.min(y),
+            _ => return Error::throw(ErrorType::InvalidOperation)
+        }))
+    }
+
+    fn apply_statistic(function: DefinedFunction, args: &[Value]) -> Result<Value, ErrorType> {
+        let number = |number: f64| Value::from(Expr::Number(number));
+        match (function, args) {
+            (DefinedFunction::Quantile, [values, q]) => {
+                Ok(number(Math::quantile(Executor::numbers(values)?, Executor::number(q)?)?))
+            }
+            (DefinedFunction::Correlation, [xs, ys]) => {
+                Ok(number(Math::correlation(Executor::numbers(xs)?, Executor::numbers(ys)?)?))
+            }
+            (DefinedFunction::Linreg, [xs, ys]) => {
+                let (slope, intercept) = Math::linreg(Executor::numbers(xs)?, Executor::numbers(ys)?)?;
+                Ok(Value::List(vec![number(slope), number(intercept)]))
+            }
+            (_, [values]) => {
+                let values = Executor::numbers(values)?;
+                Ok(number(match function {
+                    DefinedFunction::Mean => Math::mean(values)?,
+                    DefinedFunction::Median => Math::median(values)?,
+                    DefinedFunction::Mode => Math::mode(values)?,
+                    DefinedFunction::Variance => Math::variance(values)?,
+                    DefinedFunction::PVariance => Math::pvariance(values)?,
+                    DefinedFunction::Stddev => Math::stddev(values)?,
+                    _ => Math::pstddev(values)?,
+                }))
+            }
+            _ => Error::throw(ErrorType::InvalidOperation)
+        }
+    }
+
+    //amounts are decimals, floating point arguments are turned into their shortest decimal
+    fn apply_financial(&self, function: DefinedFunction, args: &[Value]) -> Result<Value, ErrorType> {
+        let precision = self.precision.map(Precision::working);
+        match (function, args) {
+            (DefinedFunction::Npv, [rate, cashflows]) => {
+                Ok(Value::Decimal(Math::npv(&Executor::decimal(rate)?, &Executor::decimals(cashflows)?, precision)?))
+            }
+            (DefinedFunction::Irr, [cashflows]) => Ok(Value::Decimal(Math::irr(&Executor::decimals(cashflows)?, precision)?)),
+            (_, [a, b, c]) => {
+                let (a, b, c) = (Executor::decimal(a)?, Executor::decimal(b)?, Executor::decimal(c)?);
+                Ok(match function {
+                    DefinedFunction::Pmt => Value::Decimal(Math::pmt(&a, &b, &c, precision)?),
+                    DefinedFunction::Fv => Value::Decimal(Math::fv(&a, &b, &c, precision)?),
+                    DefinedFunction::Pv => Value::Decimal(Math::pv(&a, &b, &c, precision)?),
+                    DefinedFunction::Compound => Value::Decimal(Math::compound(&a, &b, &c, precision)?),
+                    _ => {
+                        let rounding = self.precision.map_or(Rounding::HalfUp, |precision| precision.rounding);
+                        Value::List(Math::amortization(&a, &b, &c, rounding)?.into_iter().map(|(period, amounts)| {
+                            Value::List(std::iter::once(Value::Integer(period)).chain(amounts.into_iter().map(Value::Decimal)).collect())
+                        }).collect())
+                    }
+                })
+            }
+            _ => Error::throw(ErrorType::InvalidOperation)
+        }
+    }
+
+    //decimal with digits fractional digits, the way to turn a computed amount into money
+    //ties are rounded away from zero, or the way the precision rounds when there is one
+    fn round(&self, value: &Value, digits: i64) -> Result<Value, ErrorType> {
+        let number = match value.to_decimal() {
+            Some(number) => Some(number),
+            None => value.to_number().and_then(Decimal::from_f64)
+        };
+        match (number, u32::try_from(digits)) {
+            (Some(number), Ok(digits)) => Ok(Value::Decimal(number.round(digits, self.precision.map_or(Rounding::HalfUp, |precision| precision.rounding)))),
+            _ => Error::throw(ErrorType::InvalidOperation)
+        }
+    }
+
+    fn apply_linear_algebra(function: DefinedFunction, args: &[Value]) -> Result<Value, ErrorType> {
+        Ok(match (function, args) {
+            (DefinedFunction::Transpose, [Value::Matrix(matrix)]) => Value::Matrix(matrix.transpose()),
+            (DefinedFunction::Det, [Value::Matrix(matrix)]) => Value::from_number(matrix.determinant()?),
+            (DefinedFunction::Inverse, [Value::Matrix(matrix)]) => Value::Matrix(matrix.inverse()?),
+            (DefinedFunction::Rank, [Value::Matrix(matrix)]) => Value::Integer(matrix.rank() as i64),
+            (DefinedFunction::Linsolve, [Value::Matrix(matrix), b]) => {
+                Value::List(matrix.solve(&Executor::numbers(b)?)?.into_iter().map(Value::from_number).collect())
+            }
+            (DefinedFunction::Dot, [u, v]) => {
+                let (u, v) = (Executor::numbers(u)?, Executor::numbers(v)?);
+                if u.len() != v.len() {
+                    return Error::throw(ErrorType::ShapeMismatch);
+                }
+                Value::from_number(u.iter().zip(&v).map(|(a, b)| a * b).sum())
+            }
+            (DefinedFunction::Cross, [u, v]) => match (Executor::numbers(u)?.as_slice(), Executor::numbers(v)?.as_slice()) {
+                ([a1, a2, a3], [b1, b2, b3]) => Value::List(vec![
+                    Value::from_number(a2 * b3 - a3 * b2),
+                    Value::from_number(a3 * b1 - a1 * b3),
+                    Value::from_number(a1 * b2 - a2 * b1),
+                ]),
+                _ => return Error::throw(ErrorType::ShapeMismatch)
+            },
+            (_, args) if args.iter().any(Executor::has_text) => return Error::throw(ErrorType::TypeError),
+            _ => return Error::throw(ErrorType::InvalidOperation)
+        })
+    }
+
+    //text or booleans, also as an element of a list, which makes it no matrix
+    fn has_text(value: &Value) -> bool {
+        match value {
+            Value::Text(_) | Value::Bool(_) => true,
+            Value::List(values) => values.iter().any(Executor::has_text),
+            _ => false
+        }
+    }
+
+    //xs[i] is an element of a list or a row of a matrix, m[i, j] an element of a matrix
+    //indexes start at 1 like the bounds of a range
+    pub(super) fn index(value: Value, indexes: Vec<Value>) -> Result<Value, ErrorType> {
+        let position = |index: &Value, length: usize| match index {
+            Value::Integer(index) if *index >= 1 && *index as usize <= length => Ok(*index as usize - 1),
+            _ => Error::throw(ErrorType::InvalidOperation)
+        };
+        match (value, indexes.as_slice()) {
+            (Value::List(values), [index]) => Ok(values[position(index, values.len())?].clone()),
+            //a list of rows, such as a schedule
+            (Value::List(values), [row, column]) => {
+                let row = values[position(row, values.len())?].clone();
+                Executor::index(row, vec![column.clone()])
+            }
+            (Value::Matrix(matrix), [row]) => Ok(matrix.row(position(row, matrix.rows())?)),
+            (Value::Matrix(matrix), [row, column]) => {
+                Ok(matrix.element(position(row, matrix.rows())?, position(column, matrix.columns())?))
+            }
+            _ => Error::throw(ErrorType::InvalidOperation)
+        }
+    }
+
+    //elements of a list of numbers
+    fn numbers(value: &Value) -> Result<Vec<f64>, ErrorType> {
+        match value {
+            Value::List(values) => values.iter().map(Executor::number).collect(),
+            Value::Text(_) | Value::Bool(_) => Error::throw(ErrorType::TypeError),
+            _ => Error::throw(ErrorType::InvalidOperation)
+        }
+    }
+
+    fn decimals(value: &Value) -> Result<Vec<Decimal>, ErrorType> {
+        match value {
+            Value::List(values) => values.iter().map(Executor::decimal).collect(),
+            Value::Text(_) | Value::Bool(_) => Error::throw(ErrorType::TypeError),
+            _ => Error::throw(ErrorType::InvalidOperation)
+        }
+    }
+
+    fn decimal(value: &Value) -> Result<Decimal, ErrorType> {
+        match value.to_decimal() {
+            Some(number) => Ok(number),
+            None => Executor::number(value).and_then(|number| Decimal::from_f64(number).ok_or(ErrorType::InvalidOperation))
+        }
+    }
+
+    //text and booleans are a type error, other values without a number an invalid operation
+    fn number(value: &Value) -> Result<f64, ErrorType> {
+        match value {
+            Value::Text(_) | Value::Bool(_) => Error::throw(ErrorType::TypeError),
+            _ => value.to_number().ok_or(ErrorType::InvalidOperation)
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::Path;
+use crate::errors::{Error, ErrorType};
+use crate::executor::Executor;
+use crate::symbolic::Expr;
+use crate::value::Value;
+
+const SESSION_HEADER: &str = "# rust_abacus session";
+
+//a session file which cannot be read, or one of its lines which fails like any input
+#[derive(Debug)]
+pub enum LoadError {
+    Read(io::Error),
+    Evaluate(ErrorType),
+}
+
+impl Display for LoadError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LoadError::Read(error) => write!(f, "{}", error),
+            LoadError::Evaluate(error_type) => write!(f, "{}", Error::message(*error_type)),
+        }
+    }
+}
+
+impl Executor {
+    //write the global variables as one assignment per line
+    pub fn save_session(&self, path: &Path) -> io::Result<()> {
+        let mut content = format!("{}\n", SESSION_HEADER);
+        for (name, value) in self.vars.globals() {
+            if !Executor::is_saved(value) {
+                continue;
+            }
+            content.push_str(&format!("{} = {}\n", name, Executor::written(value)));
+        }
+        fs::write(path, content)
+    }
+
+    //value as an expression which evaluates back to it, numbers are not rounded like when they are displayed
+    //a floating point number is written in its shortest form that reads back to the same number
+    fn written(value: &Value) -> String {
+        let list = |values: Vec<String>| format!("[{}]", values.join(", "));
+        match value {
+            Value::Expression(Expr::Number(number)) => format!("float({})", number),
+            Value::List(values) => list(values.iter().map(Executor::written).collect()),
+            Value::Matrix(matrix) => list((0..matrix.rows())
+                .map(|row| list((0..matrix.columns())
+                    .map(|column| Executor::written(&Value::from(Expr::Number(matrix.get(row, column)))))
+                    .collect()))
+                .collect()),
+            Value::Complex(number) => format!("complex(float({}), float({}))", number.re, number.im),
+            Value::Quantity(quantity) => format!("{} {}", quantity.value(), quantity.unit()),
+            Value::Interval(interval) => {
+                let (center, radius) = interval.center_radius();
+                format!("float({}) ± float({})", center, radius)
+            }
+            Value::Text(text) => Value::quoted(text),
+            value => value.to_string(),
+        }
+    }
+
+    //symbolic values would be evaluated back as numbers, they are not saved
+    fn is_saved(value: &Value) -> bool {
+        match value {
+            Value::Integer(_) => true,
+            Value::Rational(_) => true,
+            Value::Bool(_) => true,
+            Value::Expression(Expr::Number(number)) => number.is_finite(),
+            Value::Expression(_) => false,
+            Value::List(values) => values.iter().all(Executor::is_saved),
+            Value::Matrix(_) => true,
+            Value::Complex(_) => true,
+            Value::Quantity(_) => true,
+            Value::Decimal(_) => true,
+            Value::Interval(_) => true,
+            Value::Date(_) => true,
+            Value::Text(_) => true,
+        }
+    }
+
+    //replay a saved session, it is evaluated as a script so a malformed line fails like any input
+    pub fn load_session(&mut self, path: &Path) -> Result<(), LoadError> {
+        let content = fs::read_to_string(path).map_err(LoadError::Read)?;
+        self.evaluate_all(&content).map_err(LoadError::Evaluate)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,184 @@
+//C interface of the executor, the contract of every function is written in include/abacus.h
+#![allow(clippy::missing_safety_doc)]
+
+use std::ffi::{c_char, c_int, CStr, CString};
+use std::panic;
+use std::ptr;
+use crate::errors::{Error, ErrorType};
+use crate::executor::Executor;
+use crate::lexer::Lexer;
+use crate::lexer::shunting_yard::ShuntingYard;
+use crate::lexer::tokens::TokenType;
+use crate::value::Value;
+
+pub const ABACUS_OK: c_int = 0;
+pub const ABACUS_SYNTAX_ERROR: c_int = 1;
+pub const ABACUS_UNEXPECTED_TOKEN: c_int = 2;
+pub const ABACUS_INVALID_OPERATION: c_int = 3;
+pub const ABACUS_UNBOUND_VARIABLE: c_int = 4;
+pub const ABACUS_SHAPE_MISMATCH: c_int = 5;
+pub const ABACUS_DIMENSION_MISMATCH: c_int = 6;
+pub const ABACUS_TYPE_ERROR: c_int = 7;
+pub const ABACUS_INVALID_ARGUMENT: c_int = 8;
+pub const ABACUS_INTERNAL_ERROR: c_int = 9;
+
+//executor handed to C with the message of the last error, empty after a success
+pub struct AbacusExecutor {
+    executor: Executor,
+    error: CString,
+}
+
+impl AbacusExecutor {
+    //run a call, an error leaves the variables as they were and is kept for abacus_last_error
+    //a panic is a bug of the calculator, it is reported as an internal error instead of unwinding into C
+    fn guarded(&mut self, call: impl FnOnce(&mut Executor) -> Result<(), (c_int, String)>) -> c_int {
+        let snapshot = self.executor.vars.snapshot();
+        let outcome = panic::catch_unwind(panic::AssertUnwindSafe(|| call(&mut self.executor)));
+        let (status, message) = match outcome {
+            Ok(Ok(())) => (ABACUS_OK, String::new()),
+            Ok(Err(failure)) => failure,
+            Err(_) => (ABACUS_INTERNAL_ERROR, "Internal error.".to_string()),
+        };
+        if status != ABACUS_OK {
+            self.executor.vars.restore(snapshot);
+        }
+        self.error = CString::new(message).unwrap_or_default();
+        status
+    }
+}
+
+fn status(error_type: ErrorType) -> c_int {
+    match error_type {
+        ErrorType::SyntaxError => ABACUS_SYNTAX_ERROR,
+        ErrorType::UnexpectedToken => ABACUS_UNEXPECTED_TOKEN,
+        ErrorType::InvalidOperation => ABACUS_INVALID_OPERATION,
+        ErrorType::UnboundVariable => ABACUS_UNBOUND_VARIABLE,
+        ErrorType::ShapeMismatch => ABACUS_SHAPE_MISMATCH,
+        ErrorType::DimensionMismatch => ABACUS_DIMENSION_MISMATCH,
+        ErrorType::TypeError => ABACUS_TYPE_ERROR,
+    }
+}
+
+fn failure(error_type: ErrorType) -> (c_int, String) {
+    (status(error_type), Error::message(error_type))
+}
+
+fn invalid_argument(what: &str) -> (c_int, String) {
+    (ABACUS_INVALID_ARGUMENT, format!("Invalid argument: {}.", what))
+}
+
+//UTF-8 text of a C string, None for a null pointer
+unsafe fn text<'a>(pointer: *const c_char) -> Option<&'a str> {
+    if pointer.is_null() {
+        return None;
+    }
+    CStr::from_ptr(pointer).to_str().ok()
+}
+
+//value written the way it is displayed, to be released with abacus_free_string
+unsafe fn write_value(value: &Value, output: *mut *mut c_char) {
+    if !output.is_null() {
+        *output = CString::new(value.to_string()).unwrap_or_default().into_raw();
+    }
+}
+
+//a name which the lexer reads as a variable, so neither a keyword nor a function
+fn is_variable_name(executor: &Executor, name: &str) -> bool {
+    let mut lexer = Lexer::with_functions(name, &executor.functions);
+    lexer.process().is_ok() && matches!(lexer.tokens.as_slice(),
+        [token] if token.get_token_type() == TokenType::Variable && token.get_value() == name)
+}
+
+//value of a single expression, several statements or an assignment are a syntax error
+fn expression_value(executor: &mut Executor, input: &str) -> Result<Value, ErrorType> {
+    let mut lexer = Lexer::with_functions(input, &executor.functions);
+    lexer.process()?;
+    let mut st = ShuntingYard::new();
+    st.process(lexer.tokens)?;
+    let statements = st.output_queue.split(|token| token.get_token_type() == TokenType::EndOfExpression)
+        .filter(|statement| !statement.is_empty())
+        .count();
+    if statements != 1 || st.output_queue.iter().any(|token| token.get_token_type() == TokenType::Name) {
+        return Error::throw(ErrorType::SyntaxError);
+    }
+    executor.execute(st.output_queue)
+}
+
+#[no_mangle]
+pub extern "C" fn abacus_create() -> *mut AbacusExecutor {
+    Box::into_raw(Box::new(AbacusExecutor { executor: Executor::new(), error: CString::default() }))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn abacus_free(executor: *mut AbacusExecutor) {
+    if !executor.is_null() {
+        drop(Box::from_raw(executor));
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn abacus_free_string(text: *mut c_char) {
+    if !text.is_null() {
+        drop(CString::from_raw(text));
+    }
+}
+
+//message of the error returned by the last call on executor, empty after a success
+#[no_mangle]
+pub unsafe extern "C" fn abacus_last_error(executor: *const AbacusExecutor) -> *const c_char {
+    match executor.as_ref() {
+        Some(executor) => executor.error.as_ptr(),
+        None => c"Invalid argument: executor.".as_ptr(),
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn abacus_evaluate(executor: *mut AbacusExecutor, input: *const c_char, result: *mut *mut c_char) -> c_int {
+    let Some(executor) = executor.as_mut() else {
+        return ABACUS_INVALID_ARGUMENT;
+    };
+    if !result.is_null() {
+        *result = ptr::null_mut();
+    }
+    executor.guarded(|executor| {
+        let input = text(input).ok_or_else(|| invalid_argument("input"))?;
+        let value = executor.evaluate(input).map_err(failure)?;
+        write_value(&value, result);
+        Ok(())
+    })
+}
+
+//bind name to the value of a single expression, e.g. "2.5", "1/3" or "\"text\""
+#[no_mangle]
+pub unsafe extern "C" fn abacus_set_variable(executor: *mut AbacusExecutor, name: *const c_char, value: *const c_char) -> c_int {
+    let Some(executor) = executor.as_mut() else {
+        return ABACUS_INVALID_ARGUMENT;
+    };
+    executor.guarded(|executor| {
+        let name = text(name).filter(|name| is_variable_name(executor, name)).ok_or_else(|| invalid_argument("name"))?;
+        let value = text(value).ok_or_else(|| invalid_argument("value"))?;
+        let value = expression_value(executor, value).map_err(failure)?;
+        executor.vars.assign(name, value);
+        Ok(())
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn abacus_get_variable(executor: *mut AbacusExecutor, name: *const c_char, value: *mut *mut c_char) -> c_int {
+    let Some(executor) = executor.as_mut() else {
+        return ABACUS_INVALID_ARGUMENT;
+    };
+    if !value.is_null() {
+        *value = ptr::null_mut();
+    }
+    executor.guarded(|executor| {
+        let name = text(name).ok_or_else(|| invalid_argument("name"))?;
+        match executor.vars.get(name) {
+            Some(bound) => {
+                write_value(&bound, value);
+                Ok(())
+            }
+            None => Err(failure(ErrorType::UnboundVariable))
+        }
+    })
+}
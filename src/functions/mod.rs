@@ -0,0 +1,90 @@
+#[cfg(unix)]
+pub mod plugin;
+
+use std::collections::HashMap;
+use std::rc::Rc;
+use crate::errors::ErrorType;
+use crate::lexer::tokens::function_token::DefinedFunction;
+use crate::value::Value;
+
+//names the lexer reads as something else than a function
+const RESERVED_NAMES: [&str; 4] = ["let", "in", "to", "mod"];
+
+//function provided by the host application, e.g. tax(amount)
+//call receives exactly arity evaluated arguments, an Err fails the evaluation with that error
+pub trait CalcFunction {
+    fn name(&self) -> &str;
+    fn arity(&self) -> usize;
+    fn call(&self, args: &[Value]) -> Result<Value, ErrorType>;
+}
+
+//what a function name stands for, one of the calculator or one registered by the host application
+#[derive(Clone)]
+pub enum Function {
+    Defined(DefinedFunction),
+    Registered(Rc<dyn CalcFunction>),
+}
+
+impl Function {
+    pub fn get_args_count(&self) -> usize {
+        match self {
+            Function::Defined(function) => function.get_args_count(),
+            Function::Registered(function) => function.arity(),
+        }
+    }
+
+    pub fn accepts(&self, args_count: usize) -> bool {
+        match self {
+            Function::Defined(function) => function.accepts(args_count),
+            Function::Registered(function) => args_count == function.arity(),
+        }
+    }
+
+    pub fn is_lazy(&self) -> bool {
+        match self {
+            Function::Defined(function) => function.is_lazy(),
+            Function::Registered(_) => false,
+        }
+    }
+}
+
+//functions the lexer resolves names through, the defined functions always come first
+#[derive(Default, Clone)]
+pub struct FunctionRegistry {
+    registered: HashMap<String, Rc<dyn CalcFunction>>,
+}
+
+impl FunctionRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    //a function registered under a name replaces the previous one
+    //a name which is not an identifier, a keyword or a defined function is an InvalidOperation
+    pub fn register(&mut self, function: impl CalcFunction + 'static) -> Result<(), ErrorType> {
+        let name = function.name().to_string();
+        let is_identifier = name.chars().next().is_some_and(char::is_alphabetic) &&
+            name.chars().all(|c| c.is_alphanumeric() || c == '_');
+        if !is_identifier || RESERVED_NAMES.contains(&name.as_str()) || DefinedFunction::is_defined_function(&name).is_some() {
+            return Err(ErrorType::InvalidOperation);
+        }
+        self.registered.insert(name, Rc::new(function));
+        Ok(())
+    }
+
+    pub fn unregister(&mut self, name: &str) -> bool {
+        self.registered.remove(name).is_some()
+    }
+
+    pub fn resolve(&self, name: &str) -> Option<Function> {
+        match DefinedFunction::is_defined_function(name) {
+            Some(function) => Some(Function::Defined(function)),
+            None => self.registered.get(name).map(|function| Function::Registered(Rc::clone(function)))
+        }
+    }
+
+    //names of the registered functions, in no particular order
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.registered.keys().map(String::as_str)
+    }
+}
@@ -0,0 +1,205 @@
+use std::ffi::{c_char, c_int, c_void, CStr, CString};
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io;
+use std::os::unix::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use crate::errors::{Error, ErrorType};
+use crate::functions::{CalcFunction, FunctionRegistry};
+use crate::value::Value;
+
+//version of the tables below, a plugin built against another one is refused
+pub const PLUGIN_VERSION: u32 = 1;
+//symbol every plugin exports, const AbacusPlugin *abacus_plugin(void) in include/abacus_plugin.h
+const ENTRY_POINT: &CStr = c"abacus_plugin";
+//more arguments than this is taken for a corrupted table
+const MAX_ARITY: u32 = 64;
+const MAX_FUNCTIONS: u32 = 4096;
+const RTLD_NOW: c_int = 2;
+
+//a function of numbers, it writes its result and returns 0, any other status is an invalid operation
+pub type PluginCall = extern "C" fn(args: *const f64, count: u32, result: *mut f64) -> c_int;
+
+#[repr(C)]
+pub struct PluginFunction {
+    pub name: *const c_char,
+    pub arity: u32,
+    pub call: Option<PluginCall>,
+}
+
+//table returned by the entry point, it must live as long as the library is loaded
+#[repr(C)]
+pub struct PluginTable {
+    pub version: u32,
+    pub count: u32,
+    pub functions: *const PluginFunction,
+}
+
+#[link(name = "dl")]
+extern "C" {
+    fn dlopen(filename: *const c_char, flags: c_int) -> *mut c_void;
+    fn dlsym(handle: *mut c_void, symbol: *const c_char) -> *mut c_void;
+    fn dlclose(handle: *mut c_void) -> c_int;
+    fn dlerror() -> *mut c_char;
+}
+
+#[derive(Debug)]
+pub enum PluginError {
+    Io(io::Error),
+    Open(String),
+    MissingEntryPoint,
+    Version(u32),
+    InvalidTable(String),
+    Registration(String),
+}
+
+impl Display for PluginError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PluginError::Io(error) => write!(f, "{}", error),
+            PluginError::Open(message) => write!(f, "{}", message),
+            PluginError::MissingEntryPoint => write!(f, "no {} symbol", ENTRY_POINT.to_string_lossy()),
+            PluginError::Version(version) => write!(f, "plugin version {}, expected {}", version, PLUGIN_VERSION),
+            PluginError::InvalidTable(message) => write!(f, "invalid function table: {}", message),
+            PluginError::Registration(name) => write!(f, "cannot register {}", name),
+        }
+    }
+}
+
+//handle of a loaded library, closed once the last of its functions is dropped
+struct Library {
+    handle: *mut c_void,
+}
+
+impl Library {
+    fn open(path: &Path) -> Result<Library, PluginError> {
+        let Ok(filename) = CString::new(path.as_os_str().as_bytes()) else {
+            return Err(PluginError::Open(format!("invalid path {}", path.display())));
+        };
+        //SAFETY: filename is a valid C string, loading runs the initializers of the library which is trusted
+        let handle = unsafe { dlopen(filename.as_ptr(), RTLD_NOW) };
+        if handle.is_null() {
+            return Err(PluginError::Open(Library::last_error()));
+        }
+        Ok(Library { handle })
+    }
+
+    fn last_error() -> String {
+        //SAFETY: dlerror returns null or a C string valid until the next dl call
+        let message = unsafe { dlerror() };
+        if message.is_null() {
+            "unknown error".to_string()
+        }
+        else {
+            unsafe { CStr::from_ptr(message) }.to_string_lossy().into_owned()
+        }
+    }
+
+    fn table(&self) -> Result<&PluginTable, PluginError> {
+        //SAFETY: handle is open, the symbol is a function returning a table according to the plugin ABI
+        let entry_point = unsafe { dlsym(self.handle, ENTRY_POINT.as_ptr()) };
+        if entry_point.is_null() {
+            return Err(PluginError::MissingEntryPoint);
+        }
+        let entry_point: extern "C" fn() -> *const PluginTable = unsafe { std::mem::transmute(entry_point) };
+        let table = entry_point();
+        //SAFETY: a non null table lives as long as the library
+        match unsafe { table.as_ref() } {
+            Some(table) => Ok(table),
+            None => Err(PluginError::InvalidTable("null table".to_string())),
+        }
+    }
+}
+
+impl Drop for Library {
+    fn drop(&mut self) {
+        //SAFETY: no function of the library is referenced anymore
+        unsafe { dlclose(self.handle) };
+    }
+}
+
+struct LoadedFunction {
+    name: String,
+    arity: usize,
+    call: PluginCall,
+    _library: Rc<Library>,
+}
+
+impl CalcFunction for LoadedFunction {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn arity(&self) -> usize {
+        self.arity
+    }
+
+    //plugins compute with floating point numbers, anything else is a type error
+    fn call(&self, args: &[Value]) -> Result<Value, ErrorType> {
+        let args: Vec<f64> = args.iter()
+            .map(|arg| arg.to_number().ok_or(ErrorType::TypeError))
+            .collect::<Result<_, _>>()?;
+        let mut result = 0.0;
+        if (self.call)(args.as_ptr(), args.len() as u32, &mut result) != 0 {
+            return Error::throw(ErrorType::InvalidOperation);
+        }
+        Ok(Value::from_number(result))
+    }
+}
+
+impl FunctionRegistry {
+    //register every function of a plugin, none of them when one is invalid, and return their names
+    pub fn load_plugin(&mut self, path: &Path) -> Result<Vec<String>, PluginError> {
+        let library = Rc::new(Library::open(path)?);
+        let table = library.table()?;
+        if table.version != PLUGIN_VERSION {
+            return Err(PluginError::Version(table.version));
+        }
+        if table.count > MAX_FUNCTIONS || (table.count > 0 && table.functions.is_null()) {
+            return Err(PluginError::InvalidTable(format!("{} functions", table.count)));
+        }
+        let functions = if table.count == 0 {
+            &[]
+        }
+        else {
+            //SAFETY: the table holds count functions, checked not null above
+            unsafe { std::slice::from_raw_parts(table.functions, table.count as usize) }
+        };
+
+        let mut registry = self.clone();
+        let mut names = Vec::new();
+        for function in functions {
+            if function.name.is_null() {
+                return Err(PluginError::InvalidTable("function without a name".to_string()));
+            }
+            //SAFETY: a non null name is a C string living as long as the library
+            let Ok(name) = unsafe { CStr::from_ptr(function.name) }.to_str() else {
+                return Err(PluginError::InvalidTable("name is not UTF-8".to_string()));
+            };
+            let (Some(call), true) = (function.call, function.arity <= MAX_ARITY) else {
+                return Err(PluginError::InvalidTable(format!("{} has no call or {} arguments", name, function.arity)));
+            };
+            let loaded = LoadedFunction { name: name.to_string(), arity: function.arity as usize, call, _library: Rc::clone(&library) };
+            //a plugin does not replace a function already there, even one of another plugin
+            if registry.resolve(name).is_some() || registry.register(loaded).is_err() {
+                return Err(PluginError::Registration(name.to_string()));
+            }
+            names.push(name.to_string());
+        }
+        *self = registry;
+        Ok(names)
+    }
+
+    //load every .so of a directory, in name order, a plugin which fails is skipped and reported
+    pub fn load_plugins(&mut self, directory: &Path) -> Result<Vec<(PathBuf, PluginError)>, PluginError> {
+        let mut paths: Vec<PathBuf> = fs::read_dir(directory).map_err(PluginError::Io)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|extension| extension == "so"))
+            .collect();
+        paths.sort();
+        Ok(paths.into_iter()
+            .filter_map(|path| self.load_plugin(&path).err().map(|error| (path, error)))
+            .collect())
+    }
+}
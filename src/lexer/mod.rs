@@ -1,33 +1,47 @@
 pub mod tokens;
-pub(crate) mod shunting_yard;
+pub mod shunting_yard;
 
 use crate::errors::{Error, ErrorType};
 use crate::lexer::tokens::{Token, TokenType, TokenTrait};
-use crate::lexer::tokens::function_token::{DefinedFunction, TokenFunction};
+use crate::functions::FunctionRegistry;
+use crate::lexer::tokens::function_token::TokenFunction;
 use crate::lexer::tokens::binary_operator_token::TokenOperator;
+use crate::value::unit::Unit;
 
 pub struct Lexer {
     input: String,
     position: usize,
     current_char: char,
+    //one entry per open parenthesis, true for the ones of a solve call
+    parentheses: Vec<bool>,
+    //names of functions, the defined ones and those registered by the host application
+    functions: FunctionRegistry,
     pub tokens: Vec<Box<dyn TokenTrait>>
 }
 
 impl Lexer {
-    pub(crate) fn new(input: &str) -> Lexer {
+    pub fn new(input: &str) -> Lexer {
+        Lexer::with_functions(input, &FunctionRegistry::new())
+    }
+
+    //names registered in functions are read as calls to them
+    pub fn with_functions(input: &str, functions: &FunctionRegistry) -> Lexer {
         let string_input = input.to_string();
         Lexer {
             input: string_input,
             position: 0,
             current_char: input.chars().nth(0).unwrap_or(' '),
+            parentheses: Vec::new(),
+            functions: functions.clone(),
             tokens: vec![]
         }
     }
 
-    pub(crate) fn process(&mut self) {
+    pub fn process(&mut self) -> Result<(), ErrorType> {
         while self.position < self.input.len() {
-            self.make_token();
+            self.make_token()?;
         }
+        Ok(())
     }
 
     fn advance(&mut self) {
@@ -39,35 +53,92 @@ impl Lexer {
         self.current_char = self.input.chars().nth(self.position).unwrap_or(' ');
     }
 
-    fn make_token(&mut self) {
-        if self.current_char.is_numeric() {
-            self.make_numer_token();
-            return;
+    fn peek(&self) -> char {
+        self.input.chars().nth(self.position + 1).unwrap_or(' ')
+    }
+
+    fn make_token(&mut self) -> Result<(), ErrorType> {
+        if self.current_char == '#' || (self.current_char == '/' && self.peek() == '/') {
+            self.skip_comment();
+            return Ok(());
+        }
+        else if self.current_char.is_numeric() {
+            return self.make_numer_token();
+        }
+        else if self.current_char == '"' {
+            self.make_text_token()?;
+        }
+        else if matches!(self.current_char, '<' | '>') || (matches!(self.current_char, '=' | '!') && self.peek() == '=') {
+            self.make_comparison_token();
         }
         else if TokenOperator::get_operator_value(&self.current_char.to_string()).is_some() {
             self.make_token_operator();
         }
         else if self.current_char == '(' {
-            let last_token = self.tokens.last();
-            match last_token {
-                Some(token) => {
-                    if token.get_token_type() == TokenType::Variable ||
-                        token.get_token_type() == TokenType::Number ||
-                        token.get_token_type() == TokenType::RightParenthesis {
-                        self.tokens.push(Box::new(TokenOperator::new(
-                            TokenType::BinaryOperator,
-                            "*".to_string(),
-                        )));
-                    }
+            if let Some(token) = self.tokens.last() {
+                if token.get_token_type() == TokenType::Variable ||
+                    token.get_token_type() == TokenType::Number ||
+                    token.get_token_type() == TokenType::RightParenthesis {
+                    self.tokens.push(Box::new(TokenOperator::new(
+                        TokenType::BinaryOperator,
+                        "*".to_string(),
+                    )));
                 }
-                None => {}
             }
+            let is_equation = self.tokens.last()
+                .is_some_and(|token| token.get_token_type() == TokenType::Function && token.get_value() == "solve");
+            self.parentheses.push(is_equation);
             self.tokens.push(Box::new(Token::new(
                 TokenType::LeftParenthesis,
                 self.current_char.to_string()
             )));
         }
+        else if self.current_char == '[' {
+            //a bracket right after a value indexes it, anywhere else it starts a list
+            let token_type = match self.tokens.last().map(|token| token.get_token_type()) {
+                Some(TokenType::Variable | TokenType::RightParenthesis | TokenType::RightBracket) => TokenType::Index,
+                Some(TokenType::Number) => {
+                    self.tokens.push(Box::new(TokenOperator::new(
+                        TokenType::BinaryOperator,
+                        "*".to_string(),
+                    )));
+                    TokenType::LeftBracket
+                }
+                _ => TokenType::LeftBracket
+            };
+            self.parentheses.push(false);
+            self.tokens.push(Box::new(Token::new(token_type, self.current_char.to_string())));
+        }
+        else if self.current_char == ']' {
+            self.parentheses.pop();
+            self.tokens.push(Box::new(Token::new(
+                TokenType::RightBracket,
+                self.current_char.to_string()
+            )));
+        }
+        else if self.current_char == '{' {
+            self.parentheses.push(false);
+            self.tokens.push(Box::new(Token::new(
+                TokenType::LeftBrace,
+                self.current_char.to_string()
+            )));
+        }
+        else if self.current_char == '}' {
+            self.parentheses.pop();
+            self.tokens.push(Box::new(Token::new(
+                TokenType::RightBrace,
+                self.current_char.to_string()
+            )));
+        }
+        else if self.current_char == '±' {
+            self.tokens.push(Box::new(Token::new(TokenType::PlusMinus, self.current_char.to_string())));
+        }
+        else if self.current_char == '.' && self.peek() == '.' {
+            self.advance();
+            self.tokens.push(Box::new(Token::new(TokenType::Range, "..".to_string())));
+        }
         else if self.current_char == ')' {
+            self.parentheses.pop();
             self.tokens.push(Box::new(Token::new(
                 TokenType::RightParenthesis,
                 self.current_char.to_string()
@@ -78,72 +149,168 @@ impl Lexer {
             match last_token {
                 Some(token) => {
                     if token.get_token_type() != TokenType::Number &&
+                        token.get_token_type() != TokenType::Date &&
+                        token.get_token_type() != TokenType::Text &&
                         token.get_token_type() != TokenType::RightParenthesis &&
-                        token.get_token_type() != TokenType::Variable {
-                        Error::throw::<()>(ErrorType::SyntaxError);
+                        token.get_token_type() != TokenType::RightBracket &&
+                        token.get_token_type() != TokenType::RightBrace &&
+                        token.get_token_type() != TokenType::Variable &&
+                        token.get_token_type() != TokenType::Unit {
+                        return Error::throw(ErrorType::SyntaxError);
                     }
                 }
-                None => Error::throw::<()>(ErrorType::SyntaxError)
+                None => return Error::throw(ErrorType::SyntaxError)
             }
             self.tokens.push(Box::new(Token::new(
                 TokenType::Comma,
                 self.current_char.to_string()
             )));
         }
-        else if self.current_char == ' ' || self.current_char == '\t' ||
-                self.current_char == '\n' || self.current_char == ';' {
-            if self.current_char == ';' {
-                self.tokens.push(Box::new(Token::new(
-                    TokenType::EndOfExpression,
-                    self.current_char.to_string()
-                )));
+        else if self.current_char == ' ' || self.current_char == '\t' || self.current_char == '\r' {
+            self.advance();
+            return Ok(());
+        }
+        else if self.current_char == ';' || self.current_char == '\n' {
+            if self.current_char == ';' || self.ends_statement_at_newline() {
+                self.make_end_of_expression();
             }
             self.advance();
-            return;
+            return Ok(());
         }
         else if self.current_char == '=' {
-            if self.tokens.len() == 0 {
-                Error::throw::<()>(ErrorType::SyntaxError);
+            //inside solve(...) '=' separates the two sides of the equation
+            if self.parentheses.last() == Some(&true) {
+                self.tokens.push(Box::new(Token::new(
+                    TokenType::Equal,
+                    self.current_char.to_string()
+                )));
             }
-            if let Some(last_token) = self.tokens.last_mut() {
-                if last_token.get_token_type() == TokenType::Variable {
-                    last_token.set_token_type(TokenType::Name);
-                }
-                else {
-                    Error::throw::<()>(ErrorType::SyntaxError);
-                }
+            else {
+                self.make_assignment()?;
             }
         }
         else if self.current_char.is_alphanumeric() || self.current_char == '_' {
             self.make_name_token();
-            return;
+            return Ok(());
         }
         else {
-            Error::throw::<()>(ErrorType::UnexpectedToken);
+            return Error::throw(ErrorType::UnexpectedToken);
         }
 
         self.advance();
+        Ok(())
+    }
+
+    //<, <=, >, >=, == or !=
+    fn make_comparison_token(&mut self) {
+        let mut comparison = self.current_char.to_string();
+        if self.peek() == '=' {
+            self.advance();
+            comparison.push('=');
+        }
+        self.tokens.push(Box::new(Token::new(TokenType::Comparison, comparison)));
+    }
+
+    fn skip_comment(&mut self) {
+        while self.position < self.input.len() && self.current_char != '\n' {
+            self.advance();
+        }
+    }
+
+    //a line break ends the statement unless it is inside parentheses or the expression is incomplete
+    fn ends_statement_at_newline(&self) -> bool {
+        if !self.parentheses.is_empty() {
+            return false;
+        }
+        match self.tokens.last() {
+            Some(token) => matches!(token.get_token_type(),
+                TokenType::Number | TokenType::Date | TokenType::Text | TokenType::Variable | TokenType::Unit |
+                TokenType::RightParenthesis | TokenType::RightBracket | TokenType::RightBrace),
+            None => false
+        }
     }
 
-    fn make_numer_token(&mut self) {
+    //empty statements are dropped so every EndOfExpression closes a statement with a result
+    fn make_end_of_expression(&mut self) {
+        match self.tokens.last() {
+            Some(token) if token.get_token_type() != TokenType::EndOfExpression => {
+                self.tokens.push(Box::new(Token::new(
+                    TokenType::EndOfExpression,
+                    ";".to_string()
+                )));
+            }
+            _ => {}
+        }
+    }
+
+    fn make_numer_token(&mut self) -> Result<(), ErrorType> {
         let mut number: String = String::new();
         let mut dot_count: usize = 0;
 
-        while self.current_char.is_numeric() || self.current_char == '.' {
+        //the dots of a range such as 1..10 are not a decimal point
+        while self.current_char.is_numeric() || (self.current_char == '.' && self.peek() != '.') {
             if self.current_char == '.' {
                 dot_count += 1;
                 if dot_count > 1 {
-                    Error::throw::<()>(ErrorType::SyntaxError);
+                    return Error::throw(ErrorType::SyntaxError);
                 }
             }
             number.push(self.current_char);
             self.advance();
         }
 
+        //a year followed by -MM-DD is a date, with an optional THH:MM or THH:MM:SS time
+        let date_length = self.date_length();
+        if number.len() == 4 && dot_count == 0 && date_length > 0 {
+            for _ in 0..date_length {
+                number.push(self.current_char);
+                self.advance();
+            }
+            self.tokens.push(Box::new(Token::new(TokenType::Date, number)));
+            return Ok(());
+        }
+
         self.tokens.push(Box::new(Token::new(
             TokenType::Number,
             number
         )));
+        Ok(())
+    }
+
+    //length of the -MM-DD of a date at the current position and of its time, 0 if there is none
+    fn date_length(&self) -> usize {
+        let rest: Vec<char> = self.input.chars().skip(self.position).take(15).collect();
+        let matches = |pattern: &str| pattern.chars().enumerate()
+            .all(|(i, expected)| rest.get(i).is_some_and(|c| if expected == '0' { c.is_ascii_digit() } else { *c == expected }));
+        ["-00-00T00:00:00", "-00-00T00:00", "-00-00"].into_iter()
+            .find(|pattern| matches(pattern))
+            .map_or(0, |pattern| pattern.len())
+    }
+
+    //text between double quotes, \" \\ \n and \t are escapes, the closing quote is the current char once done
+    fn make_text_token(&mut self) -> Result<(), ErrorType> {
+        let mut text: String = String::new();
+        loop {
+            self.advance();
+            if self.position >= self.input.len() {
+                return Error::throw(ErrorType::SyntaxError);
+            }
+            match self.current_char {
+                '"' => break,
+                '\\' => {
+                    self.advance();
+                    text.push(match self.current_char {
+                        'n' => '\n',
+                        't' => '\t',
+                        '"' | '\\' => self.current_char,
+                        _ => return Error::throw(ErrorType::SyntaxError)
+                    });
+                }
+                c => text.push(c),
+            }
+        }
+        self.tokens.push(Box::new(Token::new(TokenType::Text, text)));
+        Ok(())
     }
 
     fn make_name_token(&mut self) {
@@ -154,34 +321,142 @@ impl Lexer {
             self.advance();
         }
 
-        if DefinedFunction::is_defined_function(&name).is_some() {
+        match name.as_str() {
+            "let" => {
+                self.tokens.push(Box::new(Token::new(TokenType::Let, name)));
+                return;
+            }
+            //after a number or to, in is the inch unless it ends the bindings of a let
+            "in" if self.is_let_open() || !matches!(self.tokens.last().map(|token| token.get_token_type()),
+                Some(TokenType::Number | TokenType::Convert)) => {
+                self.tokens.push(Box::new(Token::new(TokenType::In, name)));
+                return;
+            }
+            "to" => {
+                self.tokens.push(Box::new(Token::new(TokenType::Convert, name)));
+                return;
+            }
+            "mod" => {
+                self.tokens.push(Box::new(Token::new(TokenType::Mod, name)));
+                return;
+            }
+            _ => {}
+        }
+
+        //a function name is a call only when an argument list follows, otherwise it names a variable like any other
+        let called = self.input.chars().skip(self.position).find(|c| !c.is_whitespace()) == Some('(');
+        if let Some(function) = self.functions.resolve(&name).filter(|_| called) {
             self.tokens.push(Box::new(TokenFunction::new(
                 TokenType::Function,
-                name
+                name,
+                function
             )));
             return;
         }
 
-        let mut token_type = TokenType::Variable;
-        if let Some(last_token) = self.tokens.last() {
-            match last_token.get_token_type() {
-                TokenType::Number => {
-                    self.tokens.push(Box::new(TokenOperator::new(
-                        TokenType::BinaryOperator,
-                        "*".to_string()
-                    )));
-                }
-                TokenType::Equal => token_type = TokenType::Name,
-                _ => {}
+        let last_type = self.tokens.last().map(|token| token.get_token_type());
+        if last_type == Some(TokenType::Number) {
+            self.tokens.push(Box::new(TokenOperator::new(
+                TokenType::BinaryOperator,
+                "*".to_string()
+            )));
+        }
+
+        //names are units only right after a number or 'to', 3 km is 3 * km while a lone km is a variable
+        if matches!(last_type, Some(TokenType::Number | TokenType::Convert)) && Unit::lookup(&name).is_some() {
+            //a number and its unit are a single operand, 10 m / 2 m is (10 m) / (2 m)
+            let quantity = last_type == Some(TokenType::Number);
+            if quantity {
+                let number = self.tokens.len() - 2;
+                self.tokens.insert(number, Box::new(Token::new(TokenType::LeftParenthesis, "(".to_string())));
+            }
+            self.make_unit_token(name);
+            if quantity {
+                self.tokens.push(Box::new(Token::new(TokenType::RightParenthesis, ")".to_string())));
             }
+            return;
         }
 
         self.tokens.push(Box::new(Token::new(
-            token_type,
+            TokenType::Variable,
             name
         )));
     }
 
+    //a unit with the powers and units it is multiplied or divided by written without spaces, like m/s^2 or kg*m/s^2
+    fn make_unit_token(&mut self, name: String) {
+        let rest: Vec<char> = self.input.chars().skip(self.position).collect();
+        let mut unit = name;
+        let mut length: usize = 0;
+        loop {
+            let next = length + 1;
+            let read = match rest.get(length) {
+                //an integer power, the digits end the unit
+                Some('^') => {
+                    let sign = usize::from(rest.get(next) == Some(&'-'));
+                    let digits = rest[(next + sign).min(rest.len())..].iter().take_while(|c| c.is_ascii_digit()).count();
+                    let end = next + sign + digits;
+                    match rest.get(end) {
+                        _ if digits == 0 => 0,
+                        Some(c) if *c == '.' || c.is_alphanumeric() || *c == '_' => 0,
+                        _ => end - length
+                    }
+                }
+                //another unit, not a variable or a number
+                Some('*' | '/') => {
+                    let symbol: String = rest[next..].iter().take_while(|c| c.is_alphanumeric() || **c == '_').collect();
+                    match Unit::lookup(&symbol) {
+                        Some(_) if rest[next + symbol.chars().count()..].iter().find(|c| !c.is_whitespace()) != Some(&'(') => {
+                            1 + symbol.chars().count()
+                        }
+                        _ => 0
+                    }
+                }
+                _ => 0
+            };
+            if read == 0 {
+                break;
+            }
+            unit.extend(&rest[length..length + read]);
+            length += read;
+        }
+        for _ in 0..length {
+            self.advance();
+        }
+        self.tokens.push(Box::new(Token::new(TokenType::Unit, unit)));
+    }
+
+    //a let is open while it has more bindings than in keywords
+    fn is_let_open(&self) -> bool {
+        let count = |token_type: TokenType| self.tokens.iter().filter(|token| token.get_token_type() == token_type).count();
+        count(TokenType::Let) > count(TokenType::In)
+    }
+
+    //turn the variable before '=' into an assignment target, only allowed where an expression starts
+    fn make_assignment(&mut self) -> Result<(), ErrorType> {
+        let target_index = match self.tokens.len() {
+            0 => return Error::throw(ErrorType::SyntaxError),
+            len => len - 1,
+        };
+        if self.tokens[target_index].get_token_type() != TokenType::Variable {
+            return Error::throw(ErrorType::SyntaxError);
+        }
+        if target_index > 0 {
+            match self.tokens[target_index - 1].get_token_type() {
+                TokenType::LeftParenthesis |
+                TokenType::Comma |
+                TokenType::EndOfExpression |
+                TokenType::Name |
+                TokenType::Let |
+                TokenType::In |
+                TokenType::LeftBrace => {}
+                _ => return Error::throw(ErrorType::SyntaxError),
+            }
+        }
+        self.tokens[target_index].set_token_type(TokenType::Name);
+        Ok(())
+    }
+
     fn make_token_operator(&mut self) {
         let last_token = self.tokens.last();
         let is_unary = match last_token {
@@ -193,6 +468,16 @@ impl Lexer {
                     TokenType::LeftParenthesis => true,
                     TokenType::Comma => true,
                     TokenType::Name => true,
+                    TokenType::In => true,
+                    TokenType::Equal => true,
+                    TokenType::LeftBracket => true,
+                    TokenType::Index => true,
+                    TokenType::Range => true,
+                    TokenType::Convert => true,
+                    TokenType::PlusMinus => true,
+                    TokenType::Comparison => true,
+                    TokenType::Mod => true,
+                    TokenType::LeftBrace => true,
                     _ => last_token.is_none(),
                 }
             }
@@ -1,39 +1,177 @@
 use crate::errors::{Error, ErrorType};
-use crate::lexer::tokens::{TokenTrait, TokenType};
-use crate::lexer::tokens::binary_operator_token::{Associativity, TokenOperator};
+use crate::lexer::tokens::{Token, TokenTrait, TokenType};
+use crate::lexer::tokens::binary_operator_token::{Associativity, TokenOperator, TokenOperatorValue};
+use crate::lexer::tokens::function_token::TokenFunction;
 
-pub(crate) struct ShuntingYard {
+#[derive(Default)]
+pub struct ShuntingYard {
     pub output_queue: Vec<Box<dyn TokenTrait>>,
     operator_stack: Vec<Box<dyn TokenTrait>>,
+    //commas seen in each open parenthesis or bracket, to count the arguments of a call or the elements of a list
+    group_commas: Vec<usize>,
 }
 
 impl ShuntingYard {
     pub fn new() -> Self {
         Self {
             output_queue: Vec::new(),
-            operator_stack: Vec::new()
+            operator_stack: Vec::new(),
+            group_commas: Vec::new(),
         }
     }
 
-    pub fn process(&mut self, tokens: Vec<Box<dyn TokenTrait>>){
-        for token in tokens {
-            match token.get_token_type() {
-                TokenType::Number | TokenType::Variable => {
-                    self.output_queue.push(token);
-                    if !self.operator_stack.is_empty() && self.operator_stack.last().unwrap().get_token_type() == TokenType::UnaryOperator {
-                        self.output_queue.push(self.operator_stack.pop().unwrap());
-                    }
+    pub fn process(&mut self, tokens: Vec<Box<dyn TokenTrait>>) -> Result<(), ErrorType> {
+        let mut tokens = tokens.into_iter();
+        let mut previous_type: Option<TokenType> = None;
+        //every binding of a let is name = expr, so a name must follow 'let' and the commas between bindings
+        let mut expects_binding = false;
+        while let Some(token) = tokens.next() {
+            let token_type = token.get_token_type();
+            if expects_binding && token_type != TokenType::Name {
+                return Error::throw(ErrorType::SyntaxError);
+            }
+            expects_binding = token_type == TokenType::Let;
+            //the body of a let is not empty
+            if previous_type == Some(TokenType::In) && matches!(token_type,
+                TokenType::EndOfExpression | TokenType::Comma | TokenType::RightParenthesis | TokenType::RightBracket | TokenType::RightBrace) {
+                return Error::throw(ErrorType::SyntaxError);
+            }
+            match token_type {
+                TokenType::Number | TokenType::Date | TokenType::Text | TokenType::Variable | TokenType::Unit => {
+                    self.push_operand(token);
                 }
                 TokenType::UnaryOperator => {
                     self.operator_stack.push(token);
                 }
-                TokenType::Function | TokenType::Name => {
+                TokenType::Function => {
+                    let function = token.as_any().downcast_ref::<TokenFunction>()
+                        .expect("Failed to downcast TokenFunction");
+                    if function.is_lazy() {
+                        let arguments = ShuntingYard::lazy_arguments(&mut tokens)?;
+                        self.push_operand(Box::new(function.with_arguments(arguments)));
+                    }
+                    else {
+                        self.operator_stack.push(token);
+                    }
+                }
+                TokenType::Name | TokenType::Binding => {
+                    //a name right after 'let' or a comma of its bindings is local to the let
+                    let mut token = token;
+                    if !self.operator_stack.is_empty() &&
+                        self.operator_stack.last().unwrap().get_token_type() == TokenType::Let {
+                        token.set_token_type(TokenType::Binding);
+                    }
+                    self.operator_stack.push(token);
+                }
+                TokenType::Let => {
+                    self.output_queue.push(Box::new(Token::new(TokenType::Let, token.get_value())));
+                    self.operator_stack.push(token);
+                }
+                TokenType::In => {
+                    while !self.operator_stack.is_empty() &&
+                        self.operator_stack.last().unwrap().get_token_type() != TokenType::Let {
+                        if self.operator_stack.last().unwrap().get_token_type() == TokenType::LeftParenthesis {
+                            return Error::throw(ErrorType::SyntaxError);
+                        }
+                        self.pop_operator();
+                    }
+                    if self.operator_stack.pop().is_none() {
+                        return Error::throw(ErrorType::SyntaxError);
+                    }
+                    //the scope is closed once the body has been output
+                    self.operator_stack.push(token);
+                }
+                TokenType::Mod => {
+                    self.operator_stack.push(token);
+                }
+                TokenType::LeftBrace => {
+                    //the modulus is complete, it comes out before the block which reduces by it
+                    while !self.operator_stack.is_empty() &&
+                        self.operator_stack.last().unwrap().get_token_type() != TokenType::Mod {
+                        if matches!(self.operator_stack.last().unwrap().get_token_type(),
+                         TokenType::LeftParenthesis | TokenType::Let | TokenType::LeftBracket | TokenType::Index | TokenType::LeftBrace) {
+                            return Error::throw(ErrorType::SyntaxError);
+                        }
+                        self.pop_operator();
+                    }
+                    if self.operator_stack.pop().is_none() {
+                        return Error::throw(ErrorType::SyntaxError);
+                    }
+                    self.output_queue.push(Box::new(Token::new(TokenType::LeftBrace, token.get_value())));
+                    self.operator_stack.push(token);
+                }
+                TokenType::RightBrace => {
+                    loop {
+                        match self.operator_stack.last().map(|top| top.get_token_type()) {
+                            Some(TokenType::LeftBrace) => break,
+                            None | Some(TokenType::LeftParenthesis | TokenType::Let | TokenType::LeftBracket | TokenType::Index | TokenType::Mod) => {
+                                return Error::throw(ErrorType::SyntaxError)
+                            }
+                            _ => self.pop_operator(),
+                        }
+                    }
+                    self.operator_stack.pop();
+                    //the value of the block is an operand of what surrounds it
+                    self.push_operand(token);
+                }
+                TokenType::Equal => {
+                    //both sides are complete before the equation is, so '=' comes out last
+                    while !self.operator_stack.is_empty() &&
+                        !matches!(self.operator_stack.last().unwrap().get_token_type(),
+                         TokenType::LeftParenthesis | TokenType::Let) {
+                        self.pop_operator();
+                    }
                     self.operator_stack.push(token);
                 }
                 TokenType::Comma => {
-                    if !self.operator_stack.is_empty() &&
-                        self.operator_stack.last().unwrap().get_token_type() == TokenType::Function {
-                        self.output_queue.push(self.operator_stack.pop().unwrap());
+                    while !self.operator_stack.is_empty() &&
+                        !matches!(self.operator_stack.last().unwrap().get_token_type(),
+                         TokenType::LeftParenthesis | TokenType::Let | TokenType::LeftBracket | TokenType::Index |
+                         TokenType::Mod | TokenType::LeftBrace) {
+                        self.pop_operator();
+                    }
+                    match self.operator_stack.last().map(|top| top.get_token_type()) {
+                        None | Some(TokenType::Mod | TokenType::LeftBrace) => return Error::throw(ErrorType::SyntaxError),
+                        Some(TokenType::LeftParenthesis | TokenType::LeftBracket | TokenType::Index) => {
+                            *self.group_commas.last_mut().unwrap() += 1;
+                        }
+                        Some(TokenType::Let) => expects_binding = true,
+                        _ => {}
+                    }
+                }
+                TokenType::Range | TokenType::Convert | TokenType::PlusMinus | TokenType::Comparison => {
+                    //lowest precedence, 1..n+1 ranges up to n+1, 1 km + 1 m to m converts the sum and 1 + 1 == 2 compares it
+                    while !self.operator_stack.is_empty() &&
+                        matches!(self.operator_stack.last().unwrap().get_token_type(),
+                         TokenType::BinaryOperator | TokenType::UnaryOperator) {
+                        self.pop_operator();
+                    }
+                    self.operator_stack.push(token);
+                }
+                TokenType::LeftBracket | TokenType::Index => {
+                    self.group_commas.push(0);
+                    self.operator_stack.push(token);
+                }
+                TokenType::RightBracket => {
+                    loop {
+                        match self.operator_stack.last().map(|top| top.get_token_type()) {
+                            Some(TokenType::LeftBracket | TokenType::Index) => break,
+                            None | Some(TokenType::LeftParenthesis | TokenType::Let | TokenType::Mod | TokenType::LeftBrace) => {
+                                return Error::throw(ErrorType::SyntaxError)
+                            }
+                            _ => self.pop_operator(),
+                        }
+                    }
+                    let bracket = self.operator_stack.pop().unwrap();
+                    let commas = self.group_commas.pop().unwrap();
+                    let count = match previous_type {
+                        Some(TokenType::LeftBracket) => 0,
+                        _ => commas + 1,
+                    };
+                    //the closed bracket becomes a list of count elements, or an index into the value before it
+                    match bracket.get_token_type() {
+                        TokenType::Index => self.output_queue.push(Box::new(Token::new(TokenType::Index, count.to_string()))),
+                        _ => self.push_operand(Box::new(Token::new(TokenType::List, count.to_string()))),
                     }
                 }
                 TokenType::BinaryOperator => {
@@ -50,14 +188,18 @@ impl ShuntingYard {
 
                         if o2.get_precedence() > o1.get_precedence() ||
                             (o2.get_precedence() == o1.get_precedence() && o1.get_associativity() == Associativity::Left) {
-                            self.output_queue.push(self.operator_stack.pop().unwrap());
+                            self.pop_operator();
                             continue;
                         }
                         break;
                     }
+                    if self.is_modular_power(token.as_ref()) {
+                        self.output_queue.push(Box::new(Token::new(TokenType::Exponent, String::new())));
+                    }
                     self.operator_stack.push(token);
                 }
                 TokenType::LeftParenthesis => {
+                    self.group_commas.push(0);
                     self.operator_stack.push(token);
                 }
                 TokenType::RightParenthesis => {
@@ -67,44 +209,123 @@ impl ShuntingYard {
                         let top = self.operator_stack.last().unwrap();
                         if top.get_token_type() == TokenType::LeftParenthesis {
                             self.operator_stack.pop();
+                            let commas = self.group_commas.pop().unwrap();
 
                             if !self.operator_stack.is_empty() {
                                 let top = self.operator_stack.last().unwrap();
                                 if top.get_token_type() == TokenType::Function {
-                                    self.output_queue.push(self.operator_stack.pop().unwrap());
+                                    let token = self.operator_stack.pop().unwrap();
+                                    let function = token.as_any().downcast_ref::<TokenFunction>()
+                                        .expect("Failed to downcast TokenFunction");
+                                    let count = match previous_type {
+                                        Some(TokenType::LeftParenthesis) => 0,
+                                        _ => commas + 1,
+                                    };
+                                    self.output_queue.push(Box::new(function.with_args_count(count)?));
                                 }
                             }
                             found = true;
                             break;
                         }
-                        self.output_queue.push(self.operator_stack.pop().unwrap());
+                        if matches!(top.get_token_type(),
+                         TokenType::Let | TokenType::LeftBracket | TokenType::Index | TokenType::Mod | TokenType::LeftBrace) {
+                            return Error::throw(ErrorType::SyntaxError);
+                        }
+                        self.pop_operator();
                     }
 
                     if !found && self.operator_stack.is_empty() {
-                        Error::throw::<()>(ErrorType::SyntaxError);
+                        return Error::throw(ErrorType::SyntaxError);
                     }
                 }
                 TokenType::EndOfExpression => {
                     while !self.operator_stack.is_empty() {
                         let top = self.operator_stack.last().unwrap();
-                        if top.get_token_type() == TokenType::LeftParenthesis {
-                            Error::throw::<()>(ErrorType::SyntaxError);
+                        if matches!(top.get_token_type(),
+                         TokenType::LeftParenthesis | TokenType::Let | TokenType::LeftBracket | TokenType::Index |
+                         TokenType::Mod | TokenType::LeftBrace) {
+                            return Error::throw(ErrorType::SyntaxError);
                         }
-                        self.output_queue.push(self.operator_stack.pop().unwrap());
+                        self.pop_operator();
                     }
                     self.output_queue.push(token);
                 }
-                TokenType::Equal => self.operator_stack.push(token),
+                TokenType::List => self.push_operand(token),
+                //only ever made here, they pass through when the tokens are processed again
+                TokenType::Exponent | TokenType::ExponentEnd => self.output_queue.push(token),
             }
+            previous_type = Some(token_type);
+        }
+        if expects_binding || previous_type == Some(TokenType::In) {
+            return Error::throw(ErrorType::SyntaxError);
         }
         while !self.operator_stack.is_empty() {
             let top = self.operator_stack.last().unwrap();
-            if top.get_token_type() == TokenType::LeftParenthesis {
-                Error::throw::<()>(ErrorType::SyntaxError);
+            if matches!(top.get_token_type(),
+                         TokenType::LeftParenthesis | TokenType::Let | TokenType::LeftBracket | TokenType::Index |
+                         TokenType::Mod | TokenType::LeftBrace) {
+                return Error::throw(ErrorType::SyntaxError);
             }
-            self.output_queue.push(self.operator_stack.pop().unwrap());
+            self.pop_operator();
         }
+        Ok(())
+    }
+
+    //inside a mod block the exponent of a power is a plain integer, markers around it tell the executor
+    fn is_modular_power(&self, token: &dyn TokenTrait) -> bool {
+        token.get_token_type() == TokenType::BinaryOperator &&
+            token.as_any().downcast_ref::<TokenOperator>().is_some_and(|operator| operator.operator_value == TokenOperatorValue::Power) &&
+            self.operator_stack.iter().any(|open| open.get_token_type() == TokenType::LeftBrace)
     }
 
+    fn pop_operator(&mut self) {
+        let token = self.operator_stack.pop().unwrap();
+        if self.is_modular_power(token.as_ref()) {
+            self.output_queue.push(Box::new(Token::new(TokenType::ExponentEnd, String::new())));
+        }
+        self.output_queue.push(token);
+    }
 
-}
\ No newline at end of file
+    fn push_operand(&mut self, token: Box<dyn TokenTrait>) {
+        self.output_queue.push(token);
+        if !self.operator_stack.is_empty() && self.operator_stack.last().unwrap().get_token_type() == TokenType::UnaryOperator {
+            self.pop_operator();
+        }
+    }
+
+    //take the tokens of a lazy function call up to its closing parenthesis, each argument is processed on its own
+    fn lazy_arguments(tokens: &mut impl Iterator<Item = Box<dyn TokenTrait>>) -> Result<Vec<Vec<Box<dyn TokenTrait>>>, ErrorType> {
+        match tokens.next() {
+            Some(token) if token.get_token_type() == TokenType::LeftParenthesis => {}
+            _ => return Error::throw(ErrorType::SyntaxError)
+        }
+
+        let mut arguments: Vec<Vec<Box<dyn TokenTrait>>> = vec![Vec::new()];
+        let mut depth: usize = 0;
+        let mut open_lets: usize = 0;
+        for token in tokens.by_ref() {
+            match token.get_token_type() {
+                TokenType::LeftParenthesis | TokenType::LeftBracket | TokenType::Index | TokenType::LeftBrace => depth += 1,
+                TokenType::RightBracket | TokenType::RightBrace => depth = depth.saturating_sub(1),
+                TokenType::RightParenthesis if depth == 0 => {
+                    return arguments.into_iter().map(|argument| {
+                        let mut st: ShuntingYard = ShuntingYard::new();
+                        st.process(argument)?;
+                        Ok(st.output_queue)
+                    }).collect();
+                }
+                TokenType::RightParenthesis => depth -= 1,
+                TokenType::Let if depth == 0 => open_lets += 1,
+                TokenType::In if depth == 0 => open_lets = open_lets.saturating_sub(1),
+                //commas between let bindings do not separate arguments
+                TokenType::Comma if depth == 0 && open_lets == 0 => {
+                    arguments.push(Vec::new());
+                    continue;
+                }
+                _ => {}
+            }
+            arguments.last_mut().unwrap().push(token);
+        }
+        Error::throw(ErrorType::SyntaxError)
+    }
+}
@@ -2,6 +2,9 @@ use std::any::Any;
 use std::fmt::{Debug};
 use crate::errors::{Error, ErrorType};
 use crate::lexer::tokens::{TokenTrait, TokenType};
+use crate::value::decimal::Decimal;
+use crate::value::rational::Rational;
+use crate::value::Value;
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum Associativity {
@@ -9,7 +12,7 @@ pub enum Associativity {
     Right,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Copy)]
 pub enum TokenOperatorValue {
     Plus,
     Minus,
@@ -19,6 +22,19 @@ pub enum TokenOperatorValue {
     Modulo,
 }
 
+impl TokenOperatorValue {
+    pub fn get_symbol(&self) -> &'static str {
+        match self {
+            TokenOperatorValue::Plus => "+",
+            TokenOperatorValue::Minus => "-",
+            TokenOperatorValue::Multiply => "*",
+            TokenOperatorValue::Divide => "/",
+            TokenOperatorValue::Power => "^",
+            TokenOperatorValue::Modulo => "%",
+        }
+    }
+}
+
 pub struct TokenOperator {
     pub(crate) token_type: TokenType,
     pub operator_value: TokenOperatorValue,
@@ -26,12 +42,11 @@ pub struct TokenOperator {
 }
 
 impl TokenOperator {
+    //value is one of the symbols get_operator_value knows
     pub(crate) fn new(token_type: TokenType, value: String) -> Self {
-        let operator_option: Option<TokenOperatorValue> = TokenOperator::get_operator_value(&value);
-        match operator_option {
-            Some(operator_value) => Self {token_type, value, operator_value},
-            None => Error::throw(ErrorType::SyntaxError)
-        }
+        let operator_value = TokenOperator::get_operator_value(&value)
+            .expect("Failed to read TokenOperator");
+        Self {token_type, value, operator_value}
     }
 
     pub fn get_operator_value(operator: &str) -> Option<TokenOperatorValue> {
@@ -55,7 +70,7 @@ impl TokenOperator {
             TokenOperatorValue::Divide => 2,
             TokenOperatorValue::Power => 3,
             TokenOperatorValue::Modulo => 2,
-            _ => Error::throw(ErrorType::SyntaxError)
+            TokenOperatorValue::Minus => 1,
         }
     }
 
@@ -72,29 +87,41 @@ impl TokenOperator {
         }
     }
 
-    pub fn execute(&self, left: i64, right: i64) -> i64 {
-        match self.operator_value {
-            TokenOperatorValue::Plus => left + right,
-            TokenOperatorValue::Minus if self.token_type == TokenType::BinaryOperator => left - right,
-            TokenOperatorValue::Minus if self.token_type == TokenType::UnaryOperator => -left,
-            TokenOperatorValue::Multiply => left * right,
-            TokenOperatorValue::Divide => left / right,
-            TokenOperatorValue::Power => TokenOperator::power(left, right),
-            TokenOperatorValue::Modulo => left % right,
-            _ => Error::throw(ErrorType::SyntaxError)
-        }
+    //integer operation, an inexact quotient is a fraction and a result past the i64 range an exact decimal
+    pub fn execute(&self, left: i64, right: i64) -> Result<Value, ErrorType> {
+        let (left_decimal, right_decimal) = (Decimal::from_integer(left), Decimal::from_integer(right));
+        let exact = |number: Decimal| Value::Decimal(number);
+        Ok(match self.operator_value {
+            TokenOperatorValue::Plus => left.checked_add(right).map_or_else(|| exact(left_decimal.add(&right_decimal)), Value::Integer),
+            TokenOperatorValue::Minus if self.token_type == TokenType::UnaryOperator => {
+                left.checked_neg().map_or_else(|| exact(Decimal::from_integer(0).sub(&left_decimal)), Value::Integer)
+            }
+            TokenOperatorValue::Minus => left.checked_sub(right).map_or_else(|| exact(left_decimal.sub(&right_decimal)), Value::Integer),
+            TokenOperatorValue::Multiply => left.checked_mul(right).map_or_else(|| exact(left_decimal.mul(&right_decimal)), Value::Integer),
+            TokenOperatorValue::Divide | TokenOperatorValue::Modulo if right == 0 => return Error::throw(ErrorType::InvalidOperation),
+            TokenOperatorValue::Divide => match Rational::new(left, right) {
+                Some(quotient) => Value::from_rational(quotient),
+                None => Value::from_number(left as f64 / right as f64)
+            },
+            TokenOperatorValue::Power => TokenOperator::power(left, right)?,
+            TokenOperatorValue::Modulo => Value::Integer(left.checked_rem(right).unwrap_or(0)),
+        })
     }
 
-    fn power(left: i64, right: i64) -> i64 {
-        if left == 0 && right == 0 {
-            return 1;
+    //a negative exponent gives the fraction of the inverse, or a floating point number when it does not fit
+    //a power past the range of floating point numbers is an InvalidOperation
+    fn power(left: i64, right: i64) -> Result<Value, ErrorType> {
+        if left == 0 && right < 0 {
+            return Error::throw(ErrorType::InvalidOperation);
         }
-
-        if left == 0 || right == 0 {
-            Error::throw::<()>(ErrorType::InvalidOperation);
+        if let Some(power) = Rational::from_integer(left).checked_powi(right) {
+            return Ok(Value::from_rational(power));
+        }
+        let float = (left as f64).powf(right as f64);
+        if !float.is_finite() || float == 0.0 {
+            return Error::throw(ErrorType::InvalidOperation);
         }
-        let left_f = left as f64;
-        left_f.powf(right as f64) as i64
+        Ok(if right > 0 { Value::Decimal(Decimal::from_integer(left).powi(right as u64)) } else { Value::from_number(float) })
     }
 }
 
@@ -104,7 +131,7 @@ impl TokenTrait for TokenOperator {
     }
 
     fn get_value(&self) -> String {
-        return self.value.clone();
+        self.value.clone()
     }
 
     fn set_token_type(&mut self, token_type: TokenType) {
@@ -1,9 +1,11 @@
 use std::any::Any;
 use crate::errors::{Error, ErrorType};
+use crate::functions::Function;
 use crate::lexer::tokens::{TokenTrait, TokenType};
 use crate::maths::Math;
+use crate::value::Value;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum DefinedFunction {
     Sqrt,
     Max,
@@ -12,6 +14,57 @@ pub enum DefinedFunction {
     Prime,
     Fibo,
     Gcd,
+    Sin,
+    Cos,
+    Exp,
+    Ln,
+    Diff,
+    Simplify,
+    Solve,
+    Integrate,
+    Root,
+    Sum,
+    Prod,
+    Len,
+    Mean,
+    Median,
+    Mode,
+    Variance,
+    PVariance,
+    Stddev,
+    PStddev,
+    Quantile,
+    Correlation,
+    Linreg,
+    Transpose,
+    Det,
+    Inverse,
+    Rank,
+    Linsolve,
+    Dot,
+    Cross,
+    Re,
+    Im,
+    Conj,
+    Arg,
+    Abs,
+    Sign,
+    Pmt,
+    Fv,
+    Pv,
+    Npv,
+    Irr,
+    Compound,
+    Amortize,
+    Round,
+    Weekday,
+    IsoWeek,
+    Str,
+    Format,
+    Hex,
+    Bin,
+    Float,
+    Complex,
 }
 
 impl DefinedFunction {
@@ -26,6 +79,166 @@ impl DefinedFunction {
             DefinedFunction::Prime => 1,
             DefinedFunction::Fibo => 1,
             DefinedFunction::Gcd => 2,
+            DefinedFunction::Sin => 1,
+            DefinedFunction::Cos => 1,
+            DefinedFunction::Exp => 1,
+            DefinedFunction::Ln => 1,
+            DefinedFunction::Diff => 2,
+            DefinedFunction::Simplify => 1,
+            DefinedFunction::Solve => 2,
+            DefinedFunction::Integrate => 4,
+            DefinedFunction::Root => 4,
+            DefinedFunction::Sum => 4,
+            DefinedFunction::Prod => 4,
+            DefinedFunction::Len => 1,
+            DefinedFunction::Mean => 1,
+            DefinedFunction::Median => 1,
+            DefinedFunction::Mode => 1,
+            DefinedFunction::Variance => 1,
+            DefinedFunction::PVariance => 1,
+            DefinedFunction::Stddev => 1,
+            DefinedFunction::PStddev => 1,
+            DefinedFunction::Quantile => 2,
+            DefinedFunction::Correlation => 2,
+            DefinedFunction::Linreg => 2,
+            DefinedFunction::Transpose => 1,
+            DefinedFunction::Det => 1,
+            DefinedFunction::Inverse => 1,
+            DefinedFunction::Rank => 1,
+            DefinedFunction::Linsolve => 2,
+            DefinedFunction::Dot => 2,
+            DefinedFunction::Cross => 2,
+            DefinedFunction::Re => 1,
+            DefinedFunction::Im => 1,
+            DefinedFunction::Conj => 1,
+            DefinedFunction::Arg => 1,
+            DefinedFunction::Abs => 1,
+            DefinedFunction::Sign => 1,
+            DefinedFunction::Pmt => 3,
+            DefinedFunction::Fv => 3,
+            DefinedFunction::Pv => 3,
+            DefinedFunction::Npv => 2,
+            DefinedFunction::Irr => 1,
+            DefinedFunction::Compound => 3,
+            DefinedFunction::Amortize => 3,
+            DefinedFunction::Round => 2,
+            DefinedFunction::Weekday => 1,
+            DefinedFunction::IsoWeek => 1,
+            DefinedFunction::Str => 1,
+            DefinedFunction::Format => 2,
+            DefinedFunction::Hex => 1,
+            DefinedFunction::Bin => 1,
+            DefinedFunction::Float => 1,
+            DefinedFunction::Complex => 2,
+        }
+    }
+
+    //number of arguments a call may be written with, min and max also take a single list
+    //format takes its template and any number of values
+    pub fn accepts(&self, args_count: usize) -> bool {
+        match self {
+            DefinedFunction::Max | DefinedFunction::Min | DefinedFunction::Format => args_count >= 1,
+            _ => args_count == self.get_args_count()
+        }
+    }
+
+    //statistics read every number of a list
+    pub fn is_statistic(&self) -> bool {
+        matches!(self, DefinedFunction::Mean | DefinedFunction::Median | DefinedFunction::Mode |
+            DefinedFunction::Variance | DefinedFunction::PVariance | DefinedFunction::Stddev |
+            DefinedFunction::PStddev | DefinedFunction::Quantile | DefinedFunction::Correlation |
+            DefinedFunction::Linreg)
+    }
+
+    //matrix and vector functions, which do not apply element by element
+    pub fn is_linear_algebra(&self) -> bool {
+        matches!(self, DefinedFunction::Transpose | DefinedFunction::Det | DefinedFunction::Inverse |
+            DefinedFunction::Rank | DefinedFunction::Linsolve | DefinedFunction::Dot | DefinedFunction::Cross)
+    }
+
+    //loan and investment functions, rates are per period
+    pub fn is_financial(&self) -> bool {
+        matches!(self, DefinedFunction::Pmt | DefinedFunction::Fv | DefinedFunction::Pv | DefinedFunction::Npv |
+            DefinedFunction::Irr | DefinedFunction::Compound | DefinedFunction::Amortize)
+    }
+
+    //parts of a complex number, they also take real numbers
+    pub fn is_complex_part(&self) -> bool {
+        matches!(self, DefinedFunction::Re | DefinedFunction::Im | DefinedFunction::Conj |
+            DefinedFunction::Arg | DefinedFunction::Abs)
+    }
+
+    //functions of a date
+    pub fn is_calendar(&self) -> bool {
+        matches!(self, DefinedFunction::Weekday | DefinedFunction::IsoWeek)
+    }
+
+    //lazy functions receive their arguments unevaluated, as shunting yard outputs
+    pub fn is_lazy(&self) -> bool {
+        matches!(self, DefinedFunction::Diff | DefinedFunction::Simplify | DefinedFunction::Solve |
+            DefinedFunction::Integrate | DefinedFunction::Root | DefinedFunction::Sum | DefinedFunction::Prod)
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            DefinedFunction::Sqrt => "sqrt",
+            DefinedFunction::Max => "max",
+            DefinedFunction::Min => "min",
+            DefinedFunction::Facto => "facto",
+            DefinedFunction::Prime => "isprime",
+            DefinedFunction::Fibo => "fibo",
+            DefinedFunction::Gcd => "gcd",
+            DefinedFunction::Sin => "sin",
+            DefinedFunction::Cos => "cos",
+            DefinedFunction::Exp => "exp",
+            DefinedFunction::Ln => "ln",
+            DefinedFunction::Diff => "diff",
+            DefinedFunction::Simplify => "simplify",
+            DefinedFunction::Solve => "solve",
+            DefinedFunction::Integrate => "integrate",
+            DefinedFunction::Root => "root",
+            DefinedFunction::Sum => "sum",
+            DefinedFunction::Prod => "prod",
+            DefinedFunction::Len => "len",
+            DefinedFunction::Mean => "mean",
+            DefinedFunction::Median => "median",
+            DefinedFunction::Mode => "mode",
+            DefinedFunction::Variance => "variance",
+            DefinedFunction::PVariance => "pvariance",
+            DefinedFunction::Stddev => "stddev",
+            DefinedFunction::PStddev => "pstddev",
+            DefinedFunction::Quantile => "quantile",
+            DefinedFunction::Correlation => "correlation",
+            DefinedFunction::Linreg => "linreg",
+            DefinedFunction::Transpose => "transpose",
+            DefinedFunction::Det => "det",
+            DefinedFunction::Inverse => "inverse",
+            DefinedFunction::Rank => "rank",
+            DefinedFunction::Linsolve => "linsolve",
+            DefinedFunction::Dot => "dot",
+            DefinedFunction::Cross => "cross",
+            DefinedFunction::Re => "re",
+            DefinedFunction::Im => "im",
+            DefinedFunction::Conj => "conj",
+            DefinedFunction::Arg => "arg",
+            DefinedFunction::Abs => "abs",
+            DefinedFunction::Sign => "sign",
+            DefinedFunction::Pmt => "pmt",
+            DefinedFunction::Fv => "fv",
+            DefinedFunction::Pv => "pv",
+            DefinedFunction::Npv => "npv",
+            DefinedFunction::Irr => "irr",
+            DefinedFunction::Compound => "compound",
+            DefinedFunction::Amortize => "amortize",
+            DefinedFunction::Round => "round",
+            DefinedFunction::Weekday => "weekday",
+            DefinedFunction::IsoWeek => "isoweek",
+            DefinedFunction::Str => "str",
+            DefinedFunction::Format => "format",
+            DefinedFunction::Hex => "hex",
+            DefinedFunction::Bin => "bin",
+            DefinedFunction::Float => "float",
+            DefinedFunction::Complex => "complex",
         }
     }
 
@@ -39,47 +252,128 @@ impl DefinedFunction {
             "isprime" => Some(DefinedFunction::Prime),
             "fibo" => Some(DefinedFunction::Fibo),
             "gcd" => Some(DefinedFunction::Gcd),
+            "sin" => Some(DefinedFunction::Sin),
+            "cos" => Some(DefinedFunction::Cos),
+            "exp" => Some(DefinedFunction::Exp),
+            "ln" => Some(DefinedFunction::Ln),
+            "diff" => Some(DefinedFunction::Diff),
+            "simplify" => Some(DefinedFunction::Simplify),
+            "solve" => Some(DefinedFunction::Solve),
+            "integrate" => Some(DefinedFunction::Integrate),
+            "root" => Some(DefinedFunction::Root),
+            "sum" => Some(DefinedFunction::Sum),
+            "prod" => Some(DefinedFunction::Prod),
+            "len" => Some(DefinedFunction::Len),
+            "mean" => Some(DefinedFunction::Mean),
+            "median" => Some(DefinedFunction::Median),
+            "mode" => Some(DefinedFunction::Mode),
+            "variance" => Some(DefinedFunction::Variance),
+            "pvariance" => Some(DefinedFunction::PVariance),
+            "stddev" => Some(DefinedFunction::Stddev),
+            "pstddev" => Some(DefinedFunction::PStddev),
+            "quantile" => Some(DefinedFunction::Quantile),
+            "correlation" => Some(DefinedFunction::Correlation),
+            "linreg" => Some(DefinedFunction::Linreg),
+            "transpose" => Some(DefinedFunction::Transpose),
+            "det" => Some(DefinedFunction::Det),
+            "inverse" => Some(DefinedFunction::Inverse),
+            "rank" => Some(DefinedFunction::Rank),
+            "linsolve" => Some(DefinedFunction::Linsolve),
+            "dot" => Some(DefinedFunction::Dot),
+            "cross" => Some(DefinedFunction::Cross),
+            "re" => Some(DefinedFunction::Re),
+            "im" => Some(DefinedFunction::Im),
+            "conj" => Some(DefinedFunction::Conj),
+            "arg" => Some(DefinedFunction::Arg),
+            "abs" => Some(DefinedFunction::Abs),
+            "sign" => Some(DefinedFunction::Sign),
+            "pmt" => Some(DefinedFunction::Pmt),
+            "fv" => Some(DefinedFunction::Fv),
+            "pv" => Some(DefinedFunction::Pv),
+            "npv" => Some(DefinedFunction::Npv),
+            "irr" => Some(DefinedFunction::Irr),
+            "compound" => Some(DefinedFunction::Compound),
+            "amortize" => Some(DefinedFunction::Amortize),
+            "round" => Some(DefinedFunction::Round),
+            "weekday" => Some(DefinedFunction::Weekday),
+            "isoweek" => Some(DefinedFunction::IsoWeek),
+            "str" => Some(DefinedFunction::Str),
+            "format" => Some(DefinedFunction::Format),
+            "hex" => Some(DefinedFunction::Hex),
+            "bin" => Some(DefinedFunction::Bin),
+            "float" => Some(DefinedFunction::Float),
+            "complex" => Some(DefinedFunction::Complex),
             _ => None
         }
     }
+
+    //function of integers, results which are not integers are floating point numbers
+    pub fn execute(&self, args: Vec<i64>) -> Result<Value, ErrorType> {
+        Ok(match self {
+            DefinedFunction::Sqrt => Value::from_number(Math::sqrt(args)?),
+            DefinedFunction::Max => Value::Integer(Math::max(args)?),
+            DefinedFunction::Min => Value::Integer(Math::min(args)?),
+            DefinedFunction::Facto => Math::facto(args)?,
+            DefinedFunction::Prime => Value::Integer(Math::prime(args)?),
+            DefinedFunction::Fibo => Math::fibo(args)?,
+            DefinedFunction::Gcd => Value::Integer(Math::gcd(args)?),
+            DefinedFunction::Sign => Value::Integer(Math::signum(args)?),
+            DefinedFunction::Sin => Value::from_number(Math::sin(args)?),
+            DefinedFunction::Cos => Value::from_number(Math::cos(args)?),
+            DefinedFunction::Exp => Value::from_number(Math::exp(args)?),
+            DefinedFunction::Ln => Value::from_number(Math::ln(args)?),
+            _ => return Error::throw(ErrorType::InvalidOperation),
+        })
+    }
 }
 
 pub struct TokenFunction {
     pub(crate) token_type: TokenType,
     pub(crate) value: String,
-    pub(crate) defined_function: DefinedFunction,
+    pub(crate) function: Function,
+    pub(crate) arguments: Vec<Vec<Box<dyn TokenTrait>>>,
     args_count: usize,
 }
 
 impl TokenFunction {
-    pub(crate) fn new(token_type: TokenType, value: String) -> Self {
-        let defined_function = DefinedFunction::is_defined_function(&value);
-        let args_count = match defined_function {
-            Some(defined_function) => defined_function.get_args_count(),
-            None => Error::throw(ErrorType::SyntaxError)
-        };
-
-        match defined_function {
-            Some(defined_function) => Self {token_type, value, defined_function, args_count},
-            None => Error::throw(ErrorType::SyntaxError)
+    pub(crate) fn new(token_type: TokenType, value: String, function: Function) -> Self {
+        let args_count = function.get_args_count();
+        Self {token_type, value, function, arguments: Vec::new(), args_count}
+    }
+
+    //call with the number of arguments it was written with, which must be one the function accepts
+    pub(crate) fn with_args_count(&self, args_count: usize) -> Result<Self, ErrorType> {
+        if !self.function.accepts(args_count) {
+            return Error::throw(ErrorType::SyntaxError);
         }
+        let mut function = TokenFunction::new(TokenType::Function, self.value.clone(), self.function.clone());
+        function.args_count = args_count;
+        Ok(function)
     }
 
-    pub fn execute(&self, args: Vec<i64>) -> i64 {
-        match self.defined_function {
-            DefinedFunction::Sqrt => Math::sqrt(args),
-            DefinedFunction::Max => Math::max(args),
-            DefinedFunction::Min => Math::min(args),
-            DefinedFunction::Facto => Math::facto(args),
-            DefinedFunction::Prime => Math::prime(args),
-            DefinedFunction::Fibo => Math::fibo(args),
-            DefinedFunction::Gcd => Math::gcd(args),
+    //lazy function call holding the shunting yard output of each argument
+    pub(crate) fn with_arguments(&self, arguments: Vec<Vec<Box<dyn TokenTrait>>>) -> Self {
+        let mut function = TokenFunction::new(TokenType::Function, self.value.clone(), self.function.clone());
+        function.args_count = arguments.len();
+        function.arguments = arguments;
+        function
+    }
+
+    //function of the calculator called, None for a function registered by the host application
+    pub fn defined_function(&self) -> Option<DefinedFunction> {
+        match self.function {
+            Function::Defined(function) => Some(function),
+            Function::Registered(_) => None,
         }
     }
 
     pub fn get_args_count(&self) -> usize {
         self.args_count
     }
+
+    pub fn is_lazy(&self) -> bool {
+        self.function.is_lazy()
+    }
 }
 
 impl TokenTrait for TokenFunction {
@@ -12,12 +12,31 @@ pub enum TokenType {
     UnaryOperator,
     BinaryOperator,
     Variable,
+    Unit,
     Name,
     Number,
+    Date,
+    Text,
     Comma,
     EndOfExpression,
     Function,
+    Let,
+    Binding,
+    In,
     Equal,
+    LeftBracket,
+    RightBracket,
+    Index,
+    List,
+    Range,
+    Convert,
+    PlusMinus,
+    Comparison,
+    Mod,
+    LeftBrace,
+    RightBrace,
+    Exponent,
+    ExponentEnd,
 }
 
 pub trait TokenTrait {
@@ -47,17 +66,17 @@ pub struct Token {
 
 impl Token {
     pub(crate) fn new(token_type: TokenType, value: String) -> Self {
-        return Self {token_type, value};
+        Self {token_type, value}
     }
 }
 
 impl TokenTrait for Token {
     fn get_token_type(&self) -> TokenType {
-        return self.token_type.clone();
+        self.token_type.clone()
     }
 
     fn get_value(&self) -> String {
-        return self.value.clone();
+        self.value.clone()
     }
 
     fn set_token_type(&mut self, token_type: TokenType) {
@@ -0,0 +1,8 @@
+pub mod lexer;
+pub mod executor;
+mod maths;
+pub mod errors;
+pub mod ffi;
+pub mod functions;
+pub mod symbolic;
+pub mod value;
@@ -1,21 +1,130 @@
-mod lexer;
-mod executor;
-mod maths;
-mod errors;
+mod repl;
 
-use std::{env, io};
-use crate::executor::Executor;
-use crate::lexer::Lexer;
-use crate::lexer::tokens::TokenTrait;
-use crate::lexer::shunting_yard::ShuntingYard;
+use std::{env, fs, io};
+use std::io::Read;
+use std::path::Path;
+use rust_abacus::errors::{Error, ErrorType};
+use rust_abacus::executor::Executor;
+use rust_abacus::executor::session::LoadError;
+use rust_abacus::lexer::Lexer;
+use rust_abacus::lexer::tokens::TokenTrait;
+use rust_abacus::lexer::shunting_yard::ShuntingYard;
+use rust_abacus::lexer::tokens::TokenType;
+use rust_abacus::symbolic::Expr;
+use rust_abacus::value::decimal::{Precision, Rounding};
+
+const USAGE: &str = "\
+Usage: rust_abacus [options]
+
+Reads an expression from stdin and prints its value.
+
+Options:
+  -silent              print only the result
+  -repl                evaluate stdin line by line, :save, :load, :help and :quit are commands
+  --script <file>      evaluate every statement of a file
+  --load <file>        evaluate a saved session first
+  --simplify           print each statement simplified instead of evaluated
+  --complex            allow complex results, such as sqrt(-1)
+  --interval           read [a, b] and a ± b as intervals, without it [a, b] is a list
+                       e.g. [1.9, 2.1] * [2.9, 3.1] is an interval around 6.01 only with --interval
+  --precision <digits> decimal results with that many fractional digits
+  --rounding <mode>    half-even (the default), half-up, floor or ceil, with --precision
+  --plugins <dir>      load the function plugins of a directory
+  -h, --help           print this help";
 
 fn main() {
     let args: Vec<String> = env::args().collect();
     let mut verbose: bool = true;
-    if args.len() > 1 && args[1] == "-silent" {
-        verbose = false;
+    let mut interactive: bool = false;
+    let mut session: Option<String> = None;
+    let mut script: Option<String> = None;
+    let mut simplify: bool = false;
+    let mut complex: bool = false;
+    let mut interval: bool = false;
+    let mut precision: Option<String> = None;
+    let mut rounding: Option<String> = None;
+    let mut plugins: Option<String> = None;
+    let mut args_iter = args.iter().skip(1);
+    while let Some(arg) = args_iter.next() {
+        match arg.as_str() {
+            "-silent" => verbose = false,
+            "-repl" => interactive = true,
+            "--load" => session = args_iter.next().cloned(),
+            "--script" => script = args_iter.next().cloned(),
+            "--simplify" => simplify = true,
+            "--complex" => complex = true,
+            "--interval" => interval = true,
+            "--precision" => precision = args_iter.next().cloned(),
+            "--rounding" => rounding = args_iter.next().cloned(),
+            "--plugins" => plugins = args_iter.next().cloned(),
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return;
+            }
+            _ => {}
+        }
+    }
+
+    let mut executor: Executor = Executor::new();
+    executor.complex = complex;
+    executor.interval = interval;
+    if let Some(digits) = precision {
+        executor.precision = Some(parse_precision(&digits, rounding.as_deref()));
+    }
+    //a plugin which cannot be loaded is reported and the others are still available
+    if let Some(directory) = plugins {
+        match executor.functions.load_plugins(Path::new(&directory)) {
+            Ok(failures) => failures.iter().for_each(|(path, error)| eprintln!("Cannot load plugin {}: {}", path.display(), error)),
+            Err(error) => {
+                eprintln!("Cannot read {}: {}", directory, error);
+                std::process::exit(1);
+            }
+        }
+    }
+    if let Some(path) = session {
+        match executor.load_session(Path::new(&path)) {
+            Ok(()) => {}
+            Err(LoadError::Read(error)) => {
+                eprintln!("Cannot load {}: {}", path, error);
+                std::process::exit(1);
+            }
+            Err(LoadError::Evaluate(error_type)) => Error::exit(error_type),
+        }
+    }
+
+    if let Some(path) = script {
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(error) => {
+                eprintln!("Cannot read {}: {}", path, error);
+                std::process::exit(1);
+            }
+        };
+        match executor.evaluate_all(&content) {
+            Ok(results) => results.iter().for_each(|result| println!("{}", result)),
+            Err(error_type) => Error::exit(error_type),
+        }
+        return;
+    }
+
+    if simplify {
+        if let Err(error_type) = run_simplify(&executor) {
+            Error::exit(error_type);
+        }
+        return;
     }
 
+    if interactive {
+        repl::run(&mut executor);
+        return;
+    }
+
+    if let Err(error_type) = run(&mut executor, verbose) {
+        Error::exit(error_type);
+    }
+}
+
+fn run(executor: &mut Executor, verbose: bool) -> Result<(), ErrorType> {
     // let mut user_input = "a=sqrt(150);b=gcd(845, 951)=c_;z=(c_)(b)+8;z=76z+facto(a)-(-min(b, -c))".to_string();
     let mut user_input = "7=;9+8".to_string();
     if !verbose {
@@ -24,8 +133,8 @@ fn main() {
         stdin.read_line(&mut user_input).expect("TODO: panic message");
     }
 
-    let mut lexer: Lexer = Lexer::new(&user_input);
-    lexer.process();
+    let mut lexer: Lexer = Lexer::with_functions(&user_input, &executor.functions);
+    lexer.process()?;
     let tokens: Vec<Box<dyn TokenTrait>> = lexer.tokens;
 
     if verbose {
@@ -38,7 +147,7 @@ fn main() {
     }
 
     let mut st: ShuntingYard = ShuntingYard::new();
-    st.process(tokens);
+    st.process(tokens)?;
     let tokens: Vec<Box<dyn TokenTrait>> = st.output_queue;
 
     if verbose {
@@ -49,25 +158,61 @@ fn main() {
         println!(")");
     }
 
-    let mut executor: Executor = Executor::new();
-    let result = executor.execute(tokens);
+    let result = executor.execute(tokens)?;
 
     if verbose {
-        let results = executor.token_history;
+        let results = &executor.token_history;
         print!("EXEUTOR : (");
-        for result in &results {
-            print!("{:?}, ", result);
+        for result in results {
+            print!("{}, ", result);
         }
         println!(")");
     }
 
     if verbose {
         print!("VARS : (");
-        for var in executor.vars.iter() {
-            print!("[{:?}] => {:?}, ", var.0, var.1);
+        for (name, value) in executor.vars.globals() {
+            print!("[{:?}] => {}, ", name, value);
         }
         println!(")");
     }
 
-    println!("{}", result as i64);
+    println!("{}", result);
+    Ok(())
+}
+
+//--precision digits with --rounding half-even (the default), half-up, floor or ceil
+fn parse_precision(digits: &str, rounding: Option<&str>) -> Precision {
+    let Ok(digits) = digits.parse() else {
+        eprintln!("Invalid precision: {}", digits);
+        std::process::exit(1);
+    };
+    let rounding = match rounding.map(Rounding::parse) {
+        None => Rounding::HalfEven,
+        Some(Some(rounding)) => rounding,
+        Some(None) => {
+            eprintln!("Invalid rounding: {}", rounding.unwrap_or_default());
+            std::process::exit(1);
+        }
+    };
+    Precision { digits, rounding }
+}
+
+//print each statement of the input simplified instead of evaluated
+fn run_simplify(executor: &Executor) -> Result<(), ErrorType> {
+    let mut user_input = String::new();
+    io::stdin().read_to_string(&mut user_input).expect("Failed to read the input");
+
+    let mut lexer: Lexer = Lexer::with_functions(&user_input, &executor.functions);
+    lexer.process()?;
+    let mut st: ShuntingYard = ShuntingYard::new();
+    st.process(lexer.tokens)?;
+
+    let resolve = |name: &str| executor.vars.get(name).map(Expr::try_from).transpose();
+    for statement in st.output_queue.split(|token| token.get_token_type() == TokenType::EndOfExpression) {
+        if !statement.is_empty() {
+            println!("{}", Expr::from_tokens(statement, &resolve)?.simplify());
+        }
+    }
+    Ok(())
 }
@@ -0,0 +1,178 @@
+use std::cmp::Ordering;
+use crate::errors::{Error, ErrorType};
+use crate::maths::Math;
+use crate::value::decimal::{Decimal, Precision, Rounding};
+
+//irr looks for a sign change of the net present value between these rates, IRR_LOW + IRR_STEP * IRR_STEPS is 10
+const IRR_LOW: &str = "-0.99";
+const IRR_STEP: &str = "0.005495";
+const IRR_STEPS: usize = 2000;
+//longest schedule amortization builds
+const MAX_PERIODS: i64 = 100_000;
+//fractional digits money is computed with, results are then rounded to the precision
+const WORKING_SCALE: u32 = 40;
+//digits of the amounts in a schedule
+const CENTS: u32 = 2;
+
+//amounts are decimals so money is not rounded in binary, see Decimal::approximate for the digits of a result
+//rates are per period, as a fraction: 0.05 / 12 for 5% a year paid monthly
+impl Math {
+    //payment per period which pays back present_value in periods payments
+    pub(crate) fn pmt(rate: &Decimal, periods: &Decimal, present_value: &Decimal, precision: Option<Precision>) -> Result<Decimal, ErrorType> {
+        let growth = Math::growth(rate, periods)?;
+        if rate.is_zero() {
+            return present_value.quotient(periods, precision);
+        }
+        let one = Decimal::from_integer(1);
+        Ok(Math::working_quotient(&present_value.mul(rate).mul(&growth), &growth.sub(&one))?.approximate(precision))
+    }
+
+    //value after periods payments, each one earning the rate from when it is paid
+    pub(crate) fn fv(rate: &Decimal, periods: &Decimal, payment: &Decimal, precision: Option<Precision>) -> Result<Decimal, ErrorType> {
+        let growth = Math::growth(rate, periods)?;
+        if rate.is_zero() {
+            return Ok(payment.mul(periods).fit(precision));
+        }
+        let one = Decimal::from_integer(1);
+        Ok(Math::working_quotient(&payment.mul(&growth.sub(&one)), rate)?.approximate(precision))
+    }
+
+    //value today of periods payments, the first one a period from now
+    pub(crate) fn pv(rate: &Decimal, periods: &Decimal, payment: &Decimal, precision: Option<Precision>) -> Result<Decimal, ErrorType> {
+        let growth = Math::growth(rate, periods)?;
+        if rate.is_zero() {
+            return Ok(payment.mul(periods).fit(precision));
+        }
+        let one = Decimal::from_integer(1);
+        Ok(Math::working_quotient(&payment.mul(&growth.sub(&one)), &growth.mul(rate))?.approximate(precision))
+    }
+
+    //value today of cash flows one period apart, the first one is today
+    pub(crate) fn npv(rate: &Decimal, cashflows: &[Decimal], precision: Option<Precision>) -> Result<Decimal, ErrorType> {
+        Math::require_rate(rate)?;
+        Ok(Math::discounted(rate, cashflows)?.approximate(precision))
+    }
+
+    //rate where the net present value is zero, the smallest one if there are several
+    //without both money paid and money received there is no such rate
+    pub(crate) fn irr(cashflows: &[Decimal], precision: Option<Precision>) -> Result<Decimal, ErrorType> {
+        let signs: Vec<i32> = cashflows.iter().map(Math::sign).collect();
+        if !signs.contains(&1) || !signs.contains(&-1) {
+            return Error::throw(ErrorType::InvalidOperation);
+        }
+        let step = Decimal::parse(IRR_STEP).expect("Failed to read IRR_STEP");
+        let mut previous = Decimal::parse(IRR_LOW).expect("Failed to read IRR_LOW");
+        let mut previous_npv = Math::discounted(&previous, cashflows)?;
+        for _ in 0..IRR_STEPS {
+            let rate = previous.add(&step);
+            let npv = Math::discounted(&rate, cashflows)?;
+            if npv.is_zero() {
+                return Ok(rate.approximate(precision));
+            }
+            if Math::sign(&previous_npv) * Math::sign(&npv) < 0 {
+                return Ok(Math::bisect(cashflows, previous, rate, previous_npv)?.approximate(precision));
+            }
+            (previous, previous_npv) = (rate, npv);
+        }
+        Error::throw(ErrorType::InvalidOperation)
+    }
+
+    pub(crate) fn compound(principal: &Decimal, rate: &Decimal, periods: &Decimal, precision: Option<Precision>) -> Result<Decimal, ErrorType> {
+        Ok(principal.mul(&Math::growth(rate, periods)?).approximate(precision))
+    }
+
+    //one row per period: period, payment, interest, principal repaid and balance left, amounts rounded to cents
+    pub(crate) fn amortization(rate: &Decimal, periods: &Decimal, present_value: &Decimal, rounding: Rounding) -> Result<Vec<(i64, [Decimal; 4])>, ErrorType> {
+        let count = match periods.to_integer() {
+            Some(count) if count <= MAX_PERIODS => count,
+            _ => return Error::throw(ErrorType::InvalidOperation)
+        };
+        let cents = |amount: &Decimal| amount.round(CENTS, rounding);
+        let payment = cents(&Math::pmt(rate, periods, present_value, None)?);
+        let mut balance = cents(present_value);
+        Ok((1..=count).map(|period| {
+            let interest = cents(&balance.mul(rate));
+            //the last payment clears what the rounding of the others left in the balance
+            let principal = if period == count { balance.clone() } else { payment.sub(&interest) };
+            balance = balance.sub(&principal);
+            (period, [principal.add(&interest), interest, principal, balance.clone()])
+        }).collect())
+    }
+
+    //(1 + rate)^periods with WORKING_SCALE digits, by squares for whole periods and from exp and ln otherwise
+    fn growth(rate: &Decimal, periods: &Decimal) -> Result<Decimal, ErrorType> {
+        Math::require_rate(rate)?;
+        if periods.compare(&Decimal::from_integer(0)) != Ordering::Greater {
+            return Error::throw(ErrorType::InvalidOperation);
+        }
+        let base = Decimal::from_integer(1).add(rate);
+        let Some(mut remaining) = periods.to_integer() else {
+            let exponent = periods.mul(&base.ln(WORKING_SCALE, Rounding::HalfEven)?);
+            return exponent.exp(WORKING_SCALE, Rounding::HalfEven);
+        };
+        let mut square = base;
+        let mut power = Decimal::from_integer(1);
+        while remaining > 0 {
+            if remaining % 2 == 1 {
+                power = power.mul(&square).round(WORKING_SCALE, Rounding::HalfEven);
+            }
+            remaining /= 2;
+            if remaining > 0 {
+                square = square.mul(&square).round(WORKING_SCALE, Rounding::HalfEven);
+            }
+        }
+        Ok(power)
+    }
+
+    //net present value at a rate above -1 with WORKING_SCALE digits
+    fn discounted(rate: &Decimal, cashflows: &[Decimal]) -> Result<Decimal, ErrorType> {
+        let one = Decimal::from_integer(1);
+        let discount = Math::working_quotient(&one, &one.add(rate))?;
+        let mut factor = one;
+        let mut sum = Decimal::from_integer(0);
+        for cashflow in cashflows {
+            sum = sum.add(&cashflow.mul(&factor));
+            factor = factor.mul(&discount).round(WORKING_SCALE, Rounding::HalfEven);
+        }
+        Ok(sum.round(WORKING_SCALE, Rounding::HalfEven))
+    }
+
+    //rate between low and high where the net present value changes sign, halving the bracket until it is below a digit of WORKING_SCALE
+    fn bisect(cashflows: &[Decimal], mut low: Decimal, mut high: Decimal, mut low_npv: Decimal) -> Result<Decimal, ErrorType> {
+        let half = Decimal::parse("0.5").expect("Failed to read a half");
+        let tolerance = Decimal::from_integer(1).divide(&Decimal::from_integer(10).powi(WORKING_SCALE as u64 / 2), WORKING_SCALE, Rounding::HalfEven)?;
+        while high.sub(&low).compare(&tolerance) == Ordering::Greater {
+            let middle = low.add(&high).mul(&half).round(WORKING_SCALE, Rounding::HalfEven);
+            let npv = Math::discounted(&middle, cashflows)?;
+            if npv.is_zero() {
+                return Ok(middle);
+            }
+            if Math::sign(&npv) == Math::sign(&low_npv) {
+                (low, low_npv) = (middle, npv);
+            }
+            else {
+                high = middle;
+            }
+        }
+        Ok(low)
+    }
+
+    fn working_quotient(a: &Decimal, b: &Decimal) -> Result<Decimal, ErrorType> {
+        a.divide(b, WORKING_SCALE, Rounding::HalfEven)
+    }
+
+    fn sign(number: &Decimal) -> i32 {
+        match number.compare(&Decimal::from_integer(0)) {
+            Ordering::Less => -1,
+            Ordering::Equal => 0,
+            Ordering::Greater => 1,
+        }
+    }
+
+    fn require_rate(rate: &Decimal) -> Result<(), ErrorType> {
+        if rate.compare(&Decimal::from_integer(-1)) != Ordering::Greater {
+            return Error::throw(ErrorType::InvalidOperation);
+        }
+        Ok(())
+    }
+}
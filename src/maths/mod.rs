@@ -1,82 +1,128 @@
+pub mod finance;
+pub mod modular;
+pub mod statistics;
+
 use crate::errors::{Error, ErrorType};
+use crate::value::Value;
 
 pub struct Math;
 
 impl Math {
-    pub(crate) fn sqrt(args: Vec<i64>) -> i64 {
-        if args.len() != 1 {
-            Error::throw::<()>(ErrorType::InvalidOperation);
+    pub(crate) fn sqrt(args: Vec<i64>) -> Result<f64, ErrorType> {
+        if args.len() != 1 || args[0] < 0 {
+            return Error::throw(ErrorType::InvalidOperation);
         }
         let arg = args[0] as f64;
-        arg.sqrt() as i64
+        Ok(arg.sqrt())
     }
 
-    pub(crate) fn max(args: Vec<i64>) -> i64 {
-        if args.len() != 2 {
-            Error::throw::<()>(ErrorType::InvalidOperation);
+    pub(crate) fn max(args: Vec<i64>) -> Result<i64, ErrorType> {
+        match args.into_iter().max() {
+            Some(max) => Ok(max),
+            None => Error::throw(ErrorType::InvalidOperation)
         }
-        args[0].max(args[1])
     }
 
-    pub(crate) fn min(args: Vec<i64>) -> i64 {
-        if args.len() != 2 {
-            Error::throw::<()>(ErrorType::InvalidOperation);
+    pub(crate) fn min(args: Vec<i64>) -> Result<i64, ErrorType> {
+        match args.into_iter().min() {
+            Some(min) => Ok(min),
+            None => Error::throw(ErrorType::InvalidOperation)
         }
-        args[0].min(args[1])
     }
 
-    pub(crate) fn facto(args: Vec<i64>) -> i64 {
+    //past the i64 range the factorial goes on in floating point
+    pub(crate) fn facto(args: Vec<i64>) -> Result<Value, ErrorType> {
         if args.len() != 1 || args[0] < 0 {
-            Error::throw::<()>(ErrorType::InvalidOperation);
+            return Error::throw(ErrorType::InvalidOperation);
         }
-        let mut result = 1;
-        for i in 1..(args[0] as i64 + 1) {
-            result *= i as i64;
+        let mut result = Some(1i64);
+        let mut float = 1.0;
+        for i in 1..(args[0] + 1) {
+            result = result.and_then(|result| result.checked_mul(i));
+            float *= i as f64;
         }
-        result
+        Ok(result.map_or_else(|| Value::from_number(float), Value::Integer))
     }
 
     //make isprime function and return 1 if args[0] is prime or 0 if not
-    pub(crate) fn prime(args: Vec<i64>) -> i64 {
+    pub(crate) fn prime(args: Vec<i64>) -> Result<i64, ErrorType> {
         if args.len() != 1 || args[0] < 0 {
-            Error::throw::<()>(ErrorType::InvalidOperation);
+            return Error::throw(ErrorType::InvalidOperation);
         }
         let mut result = 1;
-        for i in 2..(args[0] as i64) {
-            if args[0] as i64 % i == 0 {
+        for i in 2..args[0] {
+            if args[0] % i == 0 {
                 result = 0;
                 break;
             }
         }
-        result
+        Ok(result)
     }
 
-    pub(crate) fn fibo(args: Vec<i64>) -> i64 {
+    //past the i64 range the sequence goes on in floating point
+    pub(crate) fn fibo(args: Vec<i64>) -> Result<Value, ErrorType> {
         if args.len() != 1 || args[0] < 0 {
-            Error::throw::<()>(ErrorType::InvalidOperation);
+            return Error::throw(ErrorType::InvalidOperation);
         }
-        let mut result = 1;
-        let mut prev = 0;
-        for _ in 1..args[0] as i64 {
+        let mut result = Some(1i64);
+        let mut prev = Some(0i64);
+        let (mut float, mut float_prev) = (1.0, 0.0);
+        for _ in 1..args[0] {
             let tmp = result;
-            result += prev;
+            result = result.zip(prev).and_then(|(result, prev)| result.checked_add(prev));
             prev = tmp;
+            (float, float_prev) = (float + float_prev, float);
         }
-        result
+        Ok(result.map_or_else(|| Value::from_number(float), Value::Integer))
     }
 
-    pub(crate) fn gcd(args: Vec<i64>) -> i64 {
+    pub(crate) fn gcd(args: Vec<i64>) -> Result<i64, ErrorType> {
         if args.len() != 2 {
-            Error::throw::<()>(ErrorType::InvalidOperation);
+            return Error::throw(ErrorType::InvalidOperation);
         }
-        let mut x = args[0] as i64;
-        let mut y = args[1] as i64;
+        let mut x = args[0];
+        let mut y = args[1];
 
         while y != 0 {
             let r = x % y;
             x = y;
             y = r;
         }
-        x.abs() as i64
+        Ok(x.abs())
+    }
+
+    pub(crate) fn signum(args: Vec<i64>) -> Result<i64, ErrorType> {
+        if args.len() != 1 {
+            return Error::throw(ErrorType::InvalidOperation);
+        }
+        Ok(args[0].signum())
+    }
+
+    pub(crate) fn sin(args: Vec<i64>) -> Result<f64, ErrorType> {
+        if args.len() != 1 {
+            return Error::throw(ErrorType::InvalidOperation);
+        }
+        Ok((args[0] as f64).sin())
+    }
+
+    pub(crate) fn cos(args: Vec<i64>) -> Result<f64, ErrorType> {
+        if args.len() != 1 {
+            return Error::throw(ErrorType::InvalidOperation);
+        }
+        Ok((args[0] as f64).cos())
+    }
+
+    pub(crate) fn exp(args: Vec<i64>) -> Result<f64, ErrorType> {
+        if args.len() != 1 {
+            return Error::throw(ErrorType::InvalidOperation);
+        }
+        Ok((args[0] as f64).exp())
+    }
+
+    pub(crate) fn ln(args: Vec<i64>) -> Result<f64, ErrorType> {
+        if args.len() != 1 || args[0] <= 0 {
+            return Error::throw(ErrorType::InvalidOperation);
+        }
+        Ok((args[0] as f64).ln())
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,44 @@
+use crate::errors::{Error, ErrorType};
+use crate::maths::Math;
+
+//arithmetic modulo a positive modulus, results are between 0 and modulus - 1
+impl Math {
+    pub(crate) fn mod_reduce(number: i128, modulus: i64) -> i64 {
+        number.rem_euclid(modulus as i128) as i64
+    }
+
+    pub(crate) fn mod_mul(a: i64, b: i64, modulus: i64) -> i64 {
+        Math::mod_reduce(a as i128 * b as i128, modulus)
+    }
+
+    //base^exponent by repeated squaring, a negative exponent is a power of the inverse of base
+    pub(crate) fn mod_pow(base: i64, exponent: i64, modulus: i64) -> Result<i64, ErrorType> {
+        let mut base = if exponent < 0 { Math::mod_inverse(base, modulus)? } else { Math::mod_reduce(base as i128, modulus) };
+        let mut exponent = exponent.unsigned_abs();
+        let mut power = Math::mod_reduce(1, modulus);
+        while exponent > 0 {
+            if exponent % 2 == 1 {
+                power = Math::mod_mul(power, base, modulus);
+            }
+            base = Math::mod_mul(base, base, modulus);
+            exponent /= 2;
+        }
+        Ok(power)
+    }
+
+    //x such that number * x is 1 modulo modulus, by the extended Euclidean algorithm
+    //an InvalidOperation when number and modulus have a common divisor
+    pub(crate) fn mod_inverse(number: i64, modulus: i64) -> Result<i64, ErrorType> {
+        let (mut r0, mut r1) = (modulus as i128, Math::mod_reduce(number as i128, modulus) as i128);
+        let (mut t0, mut t1) = (0i128, 1i128);
+        while r1 != 0 {
+            let quotient = r0 / r1;
+            (r0, r1) = (r1, r0 - quotient * r1);
+            (t0, t1) = (t1, t0 - quotient * t1);
+        }
+        if r0 != 1 {
+            return Error::throw(ErrorType::InvalidOperation);
+        }
+        Ok(Math::mod_reduce(t0, modulus))
+    }
+}
@@ -0,0 +1,116 @@
+use crate::errors::{Error, ErrorType};
+use crate::maths::Math;
+
+impl Math {
+    pub(crate) fn mean(values: Vec<f64>) -> Result<f64, ErrorType> {
+        if values.is_empty() {
+            return Error::throw(ErrorType::InvalidOperation);
+        }
+        Ok(values.iter().sum::<f64>() / values.len() as f64)
+    }
+
+    pub(crate) fn median(values: Vec<f64>) -> Result<f64, ErrorType> {
+        Math::quantile(values, 0.5)
+    }
+
+    //most frequent value, the smallest one on a tie
+    pub(crate) fn mode(values: Vec<f64>) -> Result<f64, ErrorType> {
+        let sorted = Math::sorted(values)?;
+        let mut best = (sorted[0], 0);
+        let mut run = (sorted[0], 0);
+        for value in sorted {
+            run = if value == run.0 { (value, run.1 + 1) } else { (value, 1) };
+            if run.1 > best.1 {
+                best = run;
+            }
+        }
+        Ok(best.0)
+    }
+
+    //sample variance, divided by n - 1
+    pub(crate) fn variance(values: Vec<f64>) -> Result<f64, ErrorType> {
+        if values.len() < 2 {
+            return Error::throw(ErrorType::InvalidOperation);
+        }
+        let count = values.len() as f64;
+        Ok(Math::squared_deviations(values)? / (count - 1.0))
+    }
+
+    //population variance, divided by n
+    pub(crate) fn pvariance(values: Vec<f64>) -> Result<f64, ErrorType> {
+        if values.is_empty() {
+            return Error::throw(ErrorType::InvalidOperation);
+        }
+        let count = values.len() as f64;
+        Ok(Math::squared_deviations(values)? / count)
+    }
+
+    pub(crate) fn stddev(values: Vec<f64>) -> Result<f64, ErrorType> {
+        Ok(Math::variance(values)?.sqrt())
+    }
+
+    pub(crate) fn pstddev(values: Vec<f64>) -> Result<f64, ErrorType> {
+        Ok(Math::pvariance(values)?.sqrt())
+    }
+
+    //q between 0 and 1, interpolated linearly between the closest ranks
+    pub(crate) fn quantile(values: Vec<f64>, q: f64) -> Result<f64, ErrorType> {
+        if !(0.0..=1.0).contains(&q) {
+            return Error::throw(ErrorType::InvalidOperation);
+        }
+        let sorted = Math::sorted(values)?;
+        let rank = q * (sorted.len() - 1) as f64;
+        let below = rank.floor() as usize;
+        Ok(match sorted.get(below + 1) {
+            Some(above) => sorted[below] + (rank - below as f64) * (above - sorted[below]),
+            None => sorted[below],
+        })
+    }
+
+    //Pearson correlation coefficient
+    pub(crate) fn correlation(xs: Vec<f64>, ys: Vec<f64>) -> Result<f64, ErrorType> {
+        let (covariance, x_deviations, y_deviations) = Math::deviation_sums(&xs, &ys)?;
+        if x_deviations == 0.0 || y_deviations == 0.0 {
+            return Error::throw(ErrorType::InvalidOperation);
+        }
+        Ok(covariance / (x_deviations * y_deviations).sqrt())
+    }
+
+    //least squares line, as (slope, intercept)
+    pub(crate) fn linreg(xs: Vec<f64>, ys: Vec<f64>) -> Result<(f64, f64), ErrorType> {
+        let (covariance, x_deviations, _) = Math::deviation_sums(&xs, &ys)?;
+        if x_deviations == 0.0 {
+            return Error::throw(ErrorType::InvalidOperation);
+        }
+        let slope = covariance / x_deviations;
+        Ok((slope, Math::mean(ys)? - slope * Math::mean(xs)?))
+    }
+
+    fn sorted(mut values: Vec<f64>) -> Result<Vec<f64>, ErrorType> {
+        if values.is_empty() {
+            return Error::throw(ErrorType::InvalidOperation);
+        }
+        values.sort_by(f64::total_cmp);
+        Ok(values)
+    }
+
+    fn squared_deviations(values: Vec<f64>) -> Result<f64, ErrorType> {
+        let mean = Math::mean(values.clone())?;
+        Ok(values.iter().map(|value| (value - mean).powi(2)).sum())
+    }
+
+    //sums of the products of the deviations of xs and ys, and of each squared deviations
+    fn deviation_sums(xs: &[f64], ys: &[f64]) -> Result<(f64, f64, f64), ErrorType> {
+        if xs.len() != ys.len() {
+            return Error::throw(ErrorType::ShapeMismatch);
+        }
+        if xs.len() < 2 {
+            return Error::throw(ErrorType::InvalidOperation);
+        }
+        let (x_mean, y_mean) = (Math::mean(xs.to_vec())?, Math::mean(ys.to_vec())?);
+        Ok(xs.iter().zip(ys).fold((0.0, 0.0, 0.0), |(xy, xx, yy), (x, y)| {
+            let (dx, dy) = (x - x_mean, y - y_mean);
+            (xy + dx * dy, xx + dx * dx, yy + dy * dy)
+        }))
+    }
+}
@@ -0,0 +1,63 @@
+use std::io;
+use std::io::BufRead;
+use std::path::Path;
+use rust_abacus::errors::Error;
+use rust_abacus::executor::Executor;
+use rust_abacus::executor::session::LoadError;
+
+//evaluate stdin line by line, an error only discards the line that caused it
+pub fn run(executor: &mut Executor) {
+    let stdin = io::stdin();
+    for line in stdin.lock().lines() {
+        let line = line.expect("Failed to read stdin");
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(command) = line.strip_prefix(':') {
+            if !run_command(executor, command) {
+                break;
+            }
+            continue;
+        }
+
+        let snapshot = executor.vars.snapshot();
+        match executor.evaluate(line) {
+            Ok(result) => println!("{}", result),
+            Err(error_type) => {
+                executor.vars.restore(snapshot);
+                eprintln!("{}", Error::message(error_type));
+            }
+        }
+    }
+}
+
+//return false when the session should end
+fn run_command(executor: &mut Executor, command: &str) -> bool {
+    let (name, argument) = match command.split_once(' ') {
+        Some((name, argument)) => (name, argument.trim()),
+        None => (command, ""),
+    };
+    match name {
+        "save" if !argument.is_empty() => {
+            if let Err(error) = executor.save_session(Path::new(argument)) {
+                eprintln!("Cannot save {}: {}", argument, error);
+            }
+        }
+        "load" if !argument.is_empty() => {
+            let snapshot = executor.vars.snapshot();
+            match executor.load_session(Path::new(argument)) {
+                Ok(()) => {}
+                Err(LoadError::Read(error)) => eprintln!("Cannot load {}: {}", argument, error),
+                Err(LoadError::Evaluate(error_type)) => {
+                    executor.vars.restore(snapshot);
+                    eprintln!("{}", Error::message(error_type));
+                }
+            }
+        }
+        "help" => println!("{}", crate::USAGE),
+        "quit" => return false,
+        _ => eprintln!("Unknown command :{}", command),
+    }
+    true
+}
@@ -0,0 +1,66 @@
+use crate::errors::{Error, ErrorType};
+use crate::lexer::tokens::binary_operator_token::TokenOperatorValue;
+use crate::lexer::tokens::function_token::DefinedFunction;
+use crate::symbolic::Expr;
+
+impl Expr {
+    //derivative with respect to variable, an InvalidOperation where it is not differentiable
+    pub fn derivative(&self, variable: &str) -> Result<Expr, ErrorType> {
+        Ok(match self {
+            Expr::Number(_) => Expr::Number(0.0),
+            Expr::Variable(name) => Expr::Number(if name == variable { 1.0 } else { 0.0 }),
+            Expr::Negate(operand) => -operand.derivative(variable)?,
+            Expr::Binary(operator, left, right) => {
+                let (u, v) = (left.as_ref().clone(), right.as_ref().clone());
+                let (du, dv) = (u.derivative(variable)?, v.derivative(variable)?);
+                match operator {
+                    TokenOperatorValue::Plus => du + dv,
+                    TokenOperatorValue::Minus => du - dv,
+                    TokenOperatorValue::Multiply => du * v + u * dv,
+                    TokenOperatorValue::Divide => (du * v.clone() - u * dv) / Expr::pow(v, Expr::Number(2.0)),
+                    TokenOperatorValue::Power => Expr::power_derivative(u, v, du, dv, variable),
+                    //u % c only jumps where u crosses a multiple of c, its slope is the one of u
+                    TokenOperatorValue::Modulo if !v.contains(variable) => du,
+                    TokenOperatorValue::Modulo => return Error::throw(ErrorType::InvalidOperation),
+                }
+            }
+            Expr::Call(function, args) => {
+                if args.len() != 1 {
+                    return Error::throw(ErrorType::InvalidOperation);
+                }
+                let u = args[0].clone();
+                let du = u.derivative(variable)?;
+                let outer = match function {
+                    DefinedFunction::Sqrt => {
+                        Expr::Number(1.0) / (Expr::Number(2.0) * Expr::Call(DefinedFunction::Sqrt, vec![u]))
+                    }
+                    DefinedFunction::Sin => Expr::Call(DefinedFunction::Cos, vec![u]),
+                    DefinedFunction::Cos => -Expr::Call(DefinedFunction::Sin, vec![u]),
+                    DefinedFunction::Exp => Expr::Call(DefinedFunction::Exp, vec![u]),
+                    DefinedFunction::Ln => Expr::Number(1.0) / u,
+                    DefinedFunction::Float => Expr::Number(1.0),
+                    //abs bends at 0, where sign(u) is 0
+                    DefinedFunction::Abs => Expr::Call(DefinedFunction::Sign, vec![u]),
+                    DefinedFunction::Sign => Expr::Number(0.0),
+                    _ => return Error::throw(ErrorType::InvalidOperation)
+                };
+                outer * du
+            }
+        })
+    }
+
+    fn power_derivative(u: Expr, v: Expr, du: Expr, dv: Expr, variable: &str) -> Expr {
+        if !v.contains(variable) {
+            //v * u^(v - 1) * u'
+            let exponent = v.clone() - Expr::Number(1.0);
+            return v * Expr::pow(u, exponent) * du;
+        }
+        let ln_u = Expr::Call(DefinedFunction::Ln, vec![u.clone()]);
+        if !u.contains(variable) {
+            //u^v * ln(u) * v'
+            return Expr::pow(u, v) * ln_u * dv;
+        }
+        //u^v * (v' * ln(u) + v * u' / u)
+        Expr::pow(u.clone(), v.clone()) * (dv * ln_u + v * du / u)
+    }
+}
@@ -0,0 +1,88 @@
+use crate::errors::{Error, ErrorType};
+use crate::lexer::tokens::binary_operator_token::TokenOperatorValue;
+use crate::lexer::tokens::function_token::DefinedFunction;
+use crate::symbolic::Expr;
+
+//largest arguments of facto and fibo whose result still fits in an i64
+const MAX_FACTO: f64 = 20.0;
+const MAX_FIBO: f64 = 92.0;
+
+impl Expr {
+    //numeric value with variable set to value, NaN outside of the domain of a function
+    //any other free variable is NaN too, callers check there is none with require_bound
+    pub fn evaluate(&self, variable: &str, value: f64) -> f64 {
+        self.evaluate_with(Some((variable, value)))
+    }
+
+    //numeric value of an expression without free variables, like the bounds of integrate
+    pub fn constant(&self) -> Result<f64, ErrorType> {
+        self.require_bound(None)?;
+        Ok(self.evaluate_with(None))
+    }
+
+    //an UnboundVariable if a variable other than the bound one appears in the expression
+    pub(crate) fn require_bound(&self, bound: Option<&str>) -> Result<(), ErrorType> {
+        match self {
+            Expr::Number(_) => Ok(()),
+            Expr::Variable(name) if Some(name.as_str()) == bound => Ok(()),
+            Expr::Variable(_) => Error::throw(ErrorType::UnboundVariable),
+            Expr::Negate(operand) => operand.require_bound(bound),
+            Expr::Binary(_, left, right) => {
+                left.require_bound(bound)?;
+                right.require_bound(bound)
+            }
+            Expr::Call(_, args) => args.iter().try_for_each(|arg| arg.require_bound(bound)),
+        }
+    }
+
+    fn evaluate_with(&self, binding: Option<(&str, f64)>) -> f64 {
+        match self {
+            Expr::Number(number) => *number,
+            Expr::Variable(name) => match binding {
+                Some((variable, value)) if name == variable => value,
+                _ => f64::NAN
+            },
+            Expr::Negate(operand) => -operand.evaluate_with(binding),
+            Expr::Binary(operator, left, right) => {
+                let (a, b) = (left.evaluate_with(binding), right.evaluate_with(binding));
+                match operator {
+                    TokenOperatorValue::Plus => a + b,
+                    TokenOperatorValue::Minus => a - b,
+                    TokenOperatorValue::Multiply => a * b,
+                    TokenOperatorValue::Divide => a / b,
+                    TokenOperatorValue::Power => a.powf(b),
+                    TokenOperatorValue::Modulo => a % b,
+                }
+            }
+            Expr::Call(function, args) => {
+                let args: Vec<f64> = args.iter().map(|arg| arg.evaluate_with(binding)).collect();
+                Expr::evaluate_call(*function, &args)
+            }
+        }
+    }
+
+    fn evaluate_call(function: DefinedFunction, args: &[f64]) -> f64 {
+        match (function, args) {
+            (DefinedFunction::Sqrt, [x]) => x.sqrt(),
+            (DefinedFunction::Sin, [x]) => x.sin(),
+            (DefinedFunction::Cos, [x]) => x.cos(),
+            (DefinedFunction::Exp, [x]) => x.exp(),
+            (DefinedFunction::Ln, [x]) => x.ln(),
+            (DefinedFunction::Abs, [x]) => x.abs(),
+            (DefinedFunction::Sign, [x]) => if *x == 0.0 { 0.0 } else { x.signum() },
+            (DefinedFunction::Float, [x]) => *x,
+            (DefinedFunction::Re | DefinedFunction::Conj, [x]) => *x,
+            (DefinedFunction::Im, [_]) => 0.0,
+            (DefinedFunction::Arg, [x]) => if *x < 0.0 { std::f64::consts::PI } else { 0.0 },
+            (DefinedFunction::Max, [x, y]) => x.max(*y),
+            (DefinedFunction::Min, [x, y]) => x.min(*y),
+            (DefinedFunction::Facto, [x]) if *x > MAX_FACTO => f64::INFINITY,
+            (DefinedFunction::Fibo, [x]) if *x > MAX_FIBO => f64::INFINITY,
+            //the integer functions are only defined on naturals, they run like in the executor
+            _ if args.iter().all(|arg| arg.fract() == 0.0 && *arg >= 0.0) => {
+                function.execute(args.iter().map(|arg| *arg as i64).collect()).ok().and_then(|value| value.to_number()).unwrap_or(f64::NAN)
+            }
+            _ => f64::NAN
+        }
+    }
+}
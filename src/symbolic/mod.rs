@@ -0,0 +1,359 @@
+pub mod derivative;
+pub mod evaluate;
+pub mod numeric;
+pub mod simplify;
+pub mod solve;
+
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+use crate::errors::{Error, ErrorType};
+use crate::functions::Function;
+use crate::lexer::tokens::{TokenTrait, TokenType};
+use crate::lexer::tokens::binary_operator_token::{Associativity, TokenOperator, TokenOperatorValue};
+use crate::lexer::tokens::function_token::{DefinedFunction, TokenFunction};
+use crate::value::Value;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Number(f64),
+    Variable(String),
+    Negate(Box<Expr>),
+    Binary(TokenOperatorValue, Box<Expr>, Box<Expr>),
+    Call(DefinedFunction, Vec<Expr>),
+}
+
+impl Expr {
+    //build the tree of a shunting yard output, names known by resolve are replaced by their value
+    pub fn from_tokens(tokens: &[Box<dyn TokenTrait>], resolve: &dyn Fn(&str) -> Result<Option<Expr>, ErrorType>) -> Result<Expr, ErrorType> {
+        let mut stack = Expr::build(tokens, resolve)?;
+        if stack.len() != 1 {
+            return Error::throw(ErrorType::SyntaxError);
+        }
+        Ok(stack.pop().unwrap())
+    }
+
+    //equation lhs = rhs of a solve call as the expression lhs - rhs, without '=' the expression itself
+    pub(crate) fn from_equation(tokens: &[Box<dyn TokenTrait>], resolve: &dyn Fn(&str) -> Result<Option<Expr>, ErrorType>) -> Result<Expr, ErrorType> {
+        match tokens.split_last() {
+            Some((last, sides)) if last.get_token_type() == TokenType::Equal => {
+                match <[Expr; 2]>::try_from(Expr::build(sides, resolve)?) {
+                    Ok([left, right]) => Ok(left - right),
+                    Err(_) => Error::throw(ErrorType::SyntaxError)
+                }
+            }
+            _ => Expr::from_tokens(tokens, resolve)
+        }
+    }
+
+    //expressions left on the stack once every token is read
+    fn build(tokens: &[Box<dyn TokenTrait>], resolve: &dyn Fn(&str) -> Result<Option<Expr>, ErrorType>) -> Result<Vec<Expr>, ErrorType> {
+        let mut stack: Vec<Expr> = Vec::new();
+        for token in tokens {
+            let expr = match token.get_token_type() {
+                TokenType::Number => match token.get_value().parse::<f64>() {
+                    Ok(number) => Expr::Number(number),
+                    Err(_) => return Error::throw(ErrorType::SyntaxError)
+                },
+                TokenType::Variable => resolve(&token.get_value())?
+                    .unwrap_or_else(|| Expr::Variable(token.get_value())),
+                TokenType::BinaryOperator => {
+                    let operator = token.as_any().downcast_ref::<TokenOperator>()
+                        .expect("Failed to downcast TokenOperator");
+                    let right = Expr::pop(&mut stack)?;
+                    let left = Expr::pop(&mut stack)?;
+                    Expr::binary(operator.operator_value, left, right)
+                }
+                TokenType::UnaryOperator => {
+                    let operator = token.as_any().downcast_ref::<TokenOperator>()
+                        .expect("Failed to downcast TokenOperator");
+                    let operand = Expr::pop(&mut stack)?;
+                    match operator.operator_value {
+                        TokenOperatorValue::Minus => -operand,
+                        _ => operand
+                    }
+                }
+                TokenType::Function => {
+                    let function = token.as_any().downcast_ref::<TokenFunction>()
+                        .expect("Failed to downcast TokenFunction");
+                    if function.is_lazy() {
+                        Expr::apply_lazy(function, resolve)?
+                    }
+                    else {
+                        let mut args: Vec<Expr> = (0..function.get_args_count())
+                            .map(|_| Expr::pop(&mut stack))
+                            .collect::<Result<_, _>>()?;
+                        args.reverse();
+                        match &function.function {
+                            Function::Defined(defined) => Expr::Call(*defined, args),
+                            //a registered function is only known by its values, it is called on constant arguments
+                            Function::Registered(registered) => {
+                                if !args.iter().all(|arg| matches!(arg, Expr::Number(_))) {
+                                    return Error::throw(ErrorType::InvalidOperation);
+                                }
+                                let args: Vec<Value> = args.into_iter().map(Value::from).collect();
+                                Expr::try_from(registered.call(&args)?)?
+                            }
+                        }
+                    }
+                }
+                _ => return Error::throw(ErrorType::SyntaxError)
+            };
+            stack.push(expr);
+        }
+        Ok(stack)
+    }
+
+    fn pop(stack: &mut Vec<Expr>) -> Result<Expr, ErrorType> {
+        match stack.pop() {
+            Some(expr) => Ok(expr),
+            None => Error::throw(ErrorType::SyntaxError)
+        }
+    }
+
+    //symbolic functions called inside an expression, e.g. diff(diff(x^3, x), x)
+    fn apply_lazy(function: &TokenFunction, resolve: &dyn Fn(&str) -> Result<Option<Expr>, ErrorType>) -> Result<Expr, ErrorType> {
+        let Some(defined) = function.defined_function() else {
+            return Error::throw(ErrorType::InvalidOperation);
+        };
+        match defined {
+            DefinedFunction::Simplify => {
+                if function.arguments.len() != 1 {
+                    return Error::throw(ErrorType::InvalidOperation);
+                }
+                Ok(Expr::from_tokens(&function.arguments[0], resolve)?.simplify())
+            }
+            DefinedFunction::Diff => {
+                if function.arguments.len() != 2 {
+                    return Error::throw(ErrorType::InvalidOperation);
+                }
+                let variable = Expr::variable_name(&function.arguments[1])?;
+                //the variable we derive by must stay symbolic even if it is bound
+                let resolve_free = |name: &str| if name == variable { Ok(None) } else { resolve(name) };
+                Expr::from_tokens(&function.arguments[0], &resolve_free)?.derivative(&variable)
+            }
+            //inside an expression an equation must have a single solution
+            DefinedFunction::Solve => match Expr::solve_call(function, resolve)?.as_slice() {
+                [root] => Ok(root.clone()),
+                _ => Error::throw(ErrorType::InvalidOperation)
+            },
+            DefinedFunction::Integrate | DefinedFunction::Root => {
+                if function.arguments.len() != 4 {
+                    return Error::throw(ErrorType::InvalidOperation);
+                }
+                let variable = Expr::variable_name(&function.arguments[1])?;
+                let resolve_free = |name: &str| if name == variable { Ok(None) } else { resolve(name) };
+                let expr = Expr::from_tokens(&function.arguments[0], &resolve_free)?;
+                let lower = Expr::from_tokens(&function.arguments[2], resolve)?.constant()?;
+                let upper = Expr::from_tokens(&function.arguments[3], resolve)?.constant()?;
+                match defined {
+                    DefinedFunction::Integrate => Ok(Expr::Number(expr.integrate(&variable, lower, upper)?)),
+                    _ => Ok(Expr::Number(expr.root(&variable, lower, upper)?)),
+                }
+            }
+            _ => Error::throw(ErrorType::InvalidOperation)
+        }
+    }
+
+    //solutions of solve(lhs = rhs, x), the unknown stays symbolic even if it is bound
+    pub(crate) fn solve_call(function: &TokenFunction, resolve: &dyn Fn(&str) -> Result<Option<Expr>, ErrorType>) -> Result<Vec<Expr>, ErrorType> {
+        if function.arguments.len() != 2 {
+            return Error::throw(ErrorType::InvalidOperation);
+        }
+        let variable = Expr::variable_name(&function.arguments[1])?;
+        let resolve_free = |name: &str| if name == variable { Ok(None) } else { resolve(name) };
+        Expr::from_equation(&function.arguments[0], &resolve_free)?.solve(&variable)
+    }
+
+    //argument that must be a single variable name, like the x of diff(expr, x)
+    pub(crate) fn variable_name(tokens: &[Box<dyn TokenTrait>]) -> Result<String, ErrorType> {
+        match tokens {
+            [token] if token.get_token_type() == TokenType::Variable => Ok(token.get_value()),
+            _ => Error::throw(ErrorType::SyntaxError)
+        }
+    }
+
+    pub fn binary(operator: TokenOperatorValue, left: Expr, right: Expr) -> Expr {
+        match operator {
+            TokenOperatorValue::Plus => left + right,
+            TokenOperatorValue::Minus => left - right,
+            TokenOperatorValue::Multiply => left * right,
+            TokenOperatorValue::Divide => left / right,
+            TokenOperatorValue::Power => Expr::pow(left, right),
+            TokenOperatorValue::Modulo => match (&left, &right) {
+                (Expr::Number(a), Expr::Number(b)) if *b != 0.0 => Expr::Number(a % b),
+                _ => Expr::Binary(operator, Box::new(left), Box::new(right))
+            },
+        }
+    }
+
+    //left^right, folded like the arithmetic operators below
+    pub fn pow(left: Expr, right: Expr) -> Expr {
+        match (&left, &right) {
+            (Expr::Number(a), Expr::Number(b)) => Expr::Number(a.powf(*b)),
+            (_, Expr::Number(b)) if *b == 0.0 => Expr::Number(1.0),
+            (_, Expr::Number(b)) if *b == 1.0 => left,
+            (Expr::Number(a), _) if *a == 1.0 => Expr::Number(1.0),
+            _ => Expr::Binary(TokenOperatorValue::Power, Box::new(left), Box::new(right))
+        }
+    }
+
+    //true when the variable appears in the expression
+    pub fn contains(&self, variable: &str) -> bool {
+        match self {
+            Expr::Number(_) => false,
+            Expr::Variable(name) => name == variable,
+            Expr::Negate(operand) => operand.contains(variable),
+            Expr::Binary(_, left, right) => left.contains(variable) || right.contains(variable),
+            Expr::Call(_, args) => args.iter().any(|arg| arg.contains(variable)),
+        }
+    }
+
+    //operator the node is printed with, None for atoms that never need parentheses
+    fn operator(&self) -> Option<TokenOperator> {
+        match self {
+            Expr::Number(number) if *number < 0.0 => Some(TokenOperator::new(TokenType::UnaryOperator, "-".to_string())),
+            Expr::Negate(_) => Some(TokenOperator::new(TokenType::UnaryOperator, "-".to_string())),
+            Expr::Binary(operator, _, _) => Some(TokenOperator::new(TokenType::BinaryOperator, operator.get_symbol().to_string())),
+            _ => None,
+        }
+    }
+
+    //parenthesize an operand only when the parser would otherwise group it differently
+    fn fmt_operand(&self, f: &mut Formatter<'_>, parent: &TokenOperator, is_right: bool) -> std::fmt::Result {
+        let needs_parenthesis = match self.operator() {
+            Some(operator) => {
+                let binding_side = if is_right { Associativity::Left } else { Associativity::Right };
+                operator.get_precedence() < parent.get_precedence() ||
+                    (operator.get_precedence() == parent.get_precedence() && parent.get_associativity() == binding_side)
+            }
+            None => false,
+        };
+        if needs_parenthesis {
+            write!(f, "({})", self)
+        }
+        else {
+            write!(f, "{}", self)
+        }
+    }
+}
+
+//the operators fold constants and neutral elements as they build
+
+impl Add for Expr {
+    type Output = Expr;
+
+    fn add(self, right: Expr) -> Expr {
+        let left = self;
+        match (&left, &right) {
+            (Expr::Number(a), Expr::Number(b)) => Expr::Number(a + b),
+            (Expr::Number(a), _) if *a == 0.0 => right,
+            (_, Expr::Number(b)) if *b == 0.0 => left,
+            (_, Expr::Negate(operand)) => left - *operand.clone(),
+            _ => Expr::Binary(TokenOperatorValue::Plus, Box::new(left), Box::new(right))
+        }
+    }
+}
+
+impl Sub for Expr {
+    type Output = Expr;
+
+    fn sub(self, right: Expr) -> Expr {
+        let left = self;
+        match (&left, &right) {
+            (Expr::Number(a), Expr::Number(b)) => Expr::Number(a - b),
+            (Expr::Number(a), _) if *a == 0.0 => -right,
+            (_, Expr::Number(b)) if *b == 0.0 => left,
+            _ if left == right => Expr::Number(0.0),
+            (_, Expr::Negate(operand)) => left + *operand.clone(),
+            _ => Expr::Binary(TokenOperatorValue::Minus, Box::new(left), Box::new(right))
+        }
+    }
+}
+
+impl Mul for Expr {
+    type Output = Expr;
+
+    fn mul(self, right: Expr) -> Expr {
+        let left = self;
+        match (&left, &right) {
+            (Expr::Number(a), Expr::Number(b)) => Expr::Number(a * b),
+            (Expr::Number(a), _) | (_, Expr::Number(a)) if *a == 0.0 => Expr::Number(0.0),
+            (Expr::Number(a), _) if *a == 1.0 => right,
+            (_, Expr::Number(b)) if *b == 1.0 => left,
+            (Expr::Number(a), _) if *a == -1.0 => -right,
+            (_, Expr::Number(b)) if *b == -1.0 => -left,
+            (Expr::Number(a), Expr::Binary(TokenOperatorValue::Multiply, inner, rest)) => match inner.as_ref() {
+                Expr::Number(b) => Expr::Number(a * b) * *rest.clone(),
+                _ => Expr::Binary(TokenOperatorValue::Multiply, Box::new(left), Box::new(right))
+            },
+            //keep constants in front
+            (_, Expr::Number(_)) => Expr::Binary(TokenOperatorValue::Multiply, Box::new(right), Box::new(left)),
+            _ => Expr::Binary(TokenOperatorValue::Multiply, Box::new(left), Box::new(right))
+        }
+    }
+}
+
+impl Div for Expr {
+    type Output = Expr;
+
+    fn div(self, right: Expr) -> Expr {
+        let left = self;
+        match (&left, &right) {
+            //a division by zero is not folded, whoever computes its value finds it is not finite
+            (_, Expr::Number(b)) if *b == 0.0 => Expr::Binary(TokenOperatorValue::Divide, Box::new(left), Box::new(right)),
+            (Expr::Number(a), Expr::Number(b)) => Expr::Number(a / b),
+            (Expr::Number(a), _) if *a == 0.0 => Expr::Number(0.0),
+            (_, Expr::Number(b)) if *b == 1.0 => left,
+            _ if left == right => Expr::Number(1.0),
+            _ => Expr::Binary(TokenOperatorValue::Divide, Box::new(left), Box::new(right))
+        }
+    }
+}
+
+impl Neg for Expr {
+    type Output = Expr;
+
+    fn neg(self) -> Expr {
+        match self {
+            Expr::Number(a) => Expr::Number(-a),
+            Expr::Negate(operand) => *operand,
+            Expr::Binary(TokenOperatorValue::Multiply, left, right) if matches!(left.as_ref(), Expr::Number(_)) => {
+                -*left * *right
+            }
+            operand => Expr::Negate(Box::new(operand))
+        }
+    }
+}
+
+impl Display for Expr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Expr::Number(number) => write!(f, "{}", number),
+            Expr::Variable(name) => write!(f, "{}", name),
+            Expr::Negate(operand) => {
+                let operator = self.operator().unwrap();
+                write!(f, "-")?;
+                operand.fmt_operand(f, &operator, true)
+            }
+            Expr::Binary(value, left, right) => {
+                let operator = self.operator().unwrap();
+                left.fmt_operand(f, &operator, false)?;
+                match value {
+                    TokenOperatorValue::Power => write!(f, "^")?,
+                    _ => write!(f, " {} ", value.get_symbol())?,
+                }
+                right.fmt_operand(f, &operator, true)
+            }
+            Expr::Call(function, args) => {
+                write!(f, "{}(", function.get_name())?;
+                for (i, arg) in args.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", arg)?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
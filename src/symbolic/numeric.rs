@@ -0,0 +1,157 @@
+use crate::errors::{Error, ErrorType};
+use crate::symbolic::Expr;
+
+const MAX_ITERATIONS: usize = 200;
+//roots found smaller than this relative to their bracket are taken as 0
+const TOLERANCE: f64 = 1e-15;
+//intervals are halved at most this many times when integrating
+const MAX_DEPTH: usize = 30;
+const INTEGRATION_TOLERANCE: f64 = 1e-10;
+
+//Gauss-Kronrod 7-15 nodes on [0, 1] with their weights, the Gauss nodes are the odd ones
+const KRONROD_NODES: [f64; 8] = [
+    0.9914553711208126, 0.9491079123427585,
+    0.8648644233597691, 0.7415311855993945,
+    0.5860872354676911, 0.4058451513773972,
+    0.20778495500789848, 0.0,
+];
+const KRONROD_WEIGHTS: [f64; 8] = [
+    0.022935322010529224, 0.06309209262997856,
+    0.10479001032225019, 0.14065325971552592,
+    0.1690047266392679, 0.19035057806478542,
+    0.20443294007529889, 0.20948214108472782,
+];
+const GAUSS_WEIGHTS: [f64; 4] = [
+    0.1294849661688697, 0.27970539148927664,
+    0.3818300505051189, 0.4179591836734694,
+];
+
+impl Expr {
+    //definite integral over [a, b] with respect to variable, an InvalidOperation if it diverges
+    pub fn integrate(&self, variable: &str, a: f64, b: f64) -> Result<f64, ErrorType> {
+        self.require_bound(Some(variable))?;
+        let f = |x: f64| self.evaluate(variable, x);
+        let (estimate, _) = Expr::gauss_kronrod(&f, a, b);
+        let tolerance = INTEGRATION_TOLERANCE.max(1e-12 * estimate.abs());
+        let result = Expr::adaptive_integral(&f, a, b, tolerance, MAX_DEPTH);
+        if !result.is_finite() {
+            return Error::throw(ErrorType::InvalidOperation);
+        }
+        //what is left when the integrand cancels out is rounding errors of the size of the integral of |f|
+        let (magnitude, _) = Expr::gauss_kronrod(&|x: f64| f(x).abs(), a, b);
+        Ok(if result.abs() <= 1e-12 * magnitude.abs() { 0.0 } else { Expr::round_off(result) })
+    }
+
+    //root of self between lo and hi, where it must change sign
+    pub fn root(&self, variable: &str, lo: f64, hi: f64) -> Result<f64, ErrorType> {
+        self.require_bound(Some(variable))?;
+        let f = |x: f64| self.evaluate(variable, x);
+        match Expr::find_root(&f, lo, hi) {
+            Some(root) => Ok(Expr::round_off(root)),
+            None => Error::throw(ErrorType::InvalidOperation)
+        }
+    }
+
+    //halve the interval until the Kronrod and Gauss estimates agree
+    fn adaptive_integral(f: &dyn Fn(f64) -> f64, a: f64, b: f64, tolerance: f64, depth: usize) -> f64 {
+        let (integral, error) = Expr::gauss_kronrod(f, a, b);
+        if error <= tolerance || depth == 0 || !integral.is_finite() {
+            return integral;
+        }
+        let middle = 0.5 * (a + b);
+        Expr::adaptive_integral(f, a, middle, tolerance / 2.0, depth - 1) +
+            Expr::adaptive_integral(f, middle, b, tolerance / 2.0, depth - 1)
+    }
+
+    //15 point Kronrod estimate of the integral and its distance to the embedded 7 point Gauss one
+    fn gauss_kronrod(f: &dyn Fn(f64) -> f64, a: f64, b: f64) -> (f64, f64) {
+        let center = 0.5 * (a + b);
+        let half_length = 0.5 * (b - a);
+        let f_center = f(center);
+        let mut kronrod = f_center * KRONROD_WEIGHTS[7];
+        let mut gauss = f_center * GAUSS_WEIGHTS[3];
+        for j in 0..7 {
+            let offset = half_length * KRONROD_NODES[j];
+            let pair = f(center - offset) + f(center + offset);
+            kronrod += KRONROD_WEIGHTS[j] * pair;
+            if j % 2 == 1 {
+                gauss += GAUSS_WEIGHTS[j / 2] * pair;
+            }
+        }
+        (kronrod * half_length, ((kronrod - gauss) * half_length).abs())
+    }
+
+    //root of f between a and b where it changes sign, a pole also changes sign and is told apart by its value
+    pub(crate) fn find_root(f: &dyn Fn(f64) -> f64, a: f64, b: f64) -> Option<f64> {
+        let (fa, fb) = (f(a), f(b));
+        if !fa.is_finite() || !fb.is_finite() {
+            return None;
+        }
+        let scale = 1.0 + fa.abs().max(fb.abs());
+        Expr::brent(f, a, b)
+            .filter(|root| f(*root).abs() <= 1e-9 * scale)
+            .map(|root| if root.abs() <= TOLERANCE * (a.abs() + b.abs()) { 0.0 } else { root })
+    }
+
+    //numbers that are an integer up to rounding errors relative to their size are printed as one
+    //the tolerance shrinks with the number, so a nonzero number never becomes 0
+    pub(crate) fn round_off(number: f64) -> f64 {
+        if (number - number.round()).abs() < 1e-9 * number.abs() { number.round() } else { number }
+    }
+
+    //number rounded to 12 significant digits, which hides the rounding errors of a chain of operations
+    pub(crate) fn round_significant(number: f64) -> f64 {
+        format!("{:.11e}", number).parse().unwrap_or(number)
+    }
+
+    //root of f between a and b where f changes sign, None if it does not
+    fn brent(f: &dyn Fn(f64) -> f64, mut a: f64, mut b: f64) -> Option<f64> {
+        let (mut fa, mut fb) = (f(a), f(b));
+        if fa * fb > 0.0 || fa.is_nan() || fb.is_nan() {
+            return None;
+        }
+        let (mut c, mut fc) = (a, fa);
+        let (mut d, mut e) = (b - a, b - a);
+        for _ in 0..MAX_ITERATIONS {
+            if fb * fc > 0.0 {
+                (c, fc) = (a, fa);
+                (d, e) = (b - a, b - a);
+            }
+            if fc.abs() < fb.abs() {
+                (a, fa) = (b, fb);
+                (b, fb) = (c, fc);
+                (c, fc) = (a, fa);
+            }
+            let tolerance = 2.0 * f64::EPSILON * b.abs() + f64::MIN_POSITIVE;
+            let middle = 0.5 * (c - b);
+            if middle.abs() <= tolerance || fb == 0.0 {
+                return Some(b);
+            }
+            if e.abs() >= tolerance && fa.abs() > fb.abs() {
+                //inverse quadratic interpolation, or secant when only two points are known
+                let s = fb / fa;
+                let (mut p, mut q) = if a == c {
+                    (2.0 * middle * s, 1.0 - s)
+                }
+                else {
+                    let (q, r) = (fa / fc, fb / fc);
+                    (s * (2.0 * middle * q * (q - r) - (b - a) * (r - 1.0)), (q - 1.0) * (r - 1.0) * (s - 1.0))
+                };
+                if p > 0.0 { q = -q; } else { p = -p; }
+                if 2.0 * p < (3.0 * middle * q - (tolerance * q).abs()).min((e * q).abs()) {
+                    (e, d) = (d, p / q);
+                }
+                else {
+                    (e, d) = (middle, middle);
+                }
+            }
+            else {
+                (e, d) = (middle, middle);
+            }
+            (a, fa) = (b, fb);
+            b += if d.abs() > tolerance { d } else { tolerance.copysign(middle) };
+            fb = f(b);
+        }
+        Some(b)
+    }
+}
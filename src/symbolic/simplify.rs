@@ -0,0 +1,208 @@
+use crate::lexer::tokens::binary_operator_token::TokenOperatorValue;
+use crate::lexer::tokens::function_token::DefinedFunction;
+use crate::symbolic::Expr;
+
+//rewriting stops earlier once a pass leaves the expression unchanged
+const MAX_PASSES: usize = 16;
+
+impl Expr {
+    //fold constants, drop neutral elements, collect like terms and powers of the same base
+    pub fn simplify(&self) -> Expr {
+        let mut current = self.clone();
+        for _ in 0..MAX_PASSES {
+            let next = current.simplify_pass();
+            if next == current {
+                break;
+            }
+            current = next;
+        }
+        current
+    }
+
+    fn simplify_pass(&self) -> Expr {
+        match self {
+            Expr::Number(_) | Expr::Variable(_) => self.clone(),
+            Expr::Negate(operand) => -operand.simplify_pass(),
+            Expr::Call(function, args) => {
+                let args: Vec<Expr> = args.iter().map(Expr::simplify_pass).collect();
+                match (Expr::fold_call(*function, &args), function, args.as_slice()) {
+                    (Some(number), ..) => Expr::Number(number),
+                    (None, DefinedFunction::Sqrt, [Expr::Number(argument)]) => Expr::split_sqrt(*argument, Expr::Number(1.0)),
+                    (None, DefinedFunction::Sqrt, [Expr::Binary(TokenOperatorValue::Multiply, left, right)]) => match left.as_ref() {
+                        Expr::Number(argument) if *argument > 0.0 => Expr::split_sqrt(*argument, right.as_ref().clone()),
+                        _ => Expr::Call(*function, args)
+                    },
+                    _ => Expr::Call(*function, args)
+                }
+            }
+            Expr::Binary(operator, left, right) => {
+                let expr = Expr::binary(*operator, left.simplify_pass(), right.simplify_pass());
+                match expr {
+                    Expr::Binary(TokenOperatorValue::Plus | TokenOperatorValue::Minus, ..) => {
+                        let mut terms: Vec<(f64, Expr)> = Vec::new();
+                        expr.collect_terms(1.0, &mut terms);
+                        Expr::from_terms(terms)
+                    }
+                    expr if expr.is_product() => {
+                        let mut coefficient = 1.0;
+                        let mut factors: Vec<(Expr, Expr)> = Vec::new();
+                        expr.collect_factors(&mut coefficient, &mut factors);
+                        Expr::from_factors(coefficient, factors)
+                    }
+                    expr => expr
+                }
+            }
+        }
+    }
+
+    //products, and divisions by a constant which are products by its inverse
+    fn is_product(&self) -> bool {
+        match self {
+            Expr::Binary(TokenOperatorValue::Multiply, ..) => true,
+            Expr::Binary(TokenOperatorValue::Divide, _, right) => matches!(right.as_ref(), Expr::Number(_)),
+            _ => false
+        }
+    }
+
+    //functions are only folded when the result is exact, sqrt(4) but not sqrt(2)
+    fn fold_call(function: DefinedFunction, args: &[Expr]) -> Option<f64> {
+        let argument = match args {
+            [Expr::Number(argument)] => *argument,
+            _ => return None
+        };
+        let result = match function {
+            DefinedFunction::Sqrt if argument >= 0.0 => argument.sqrt(),
+            DefinedFunction::Sin => argument.sin(),
+            DefinedFunction::Cos => argument.cos(),
+            DefinedFunction::Exp => argument.exp(),
+            DefinedFunction::Ln if argument > 0.0 => argument.ln(),
+            DefinedFunction::Abs => argument.abs(),
+            DefinedFunction::Sign if argument == 0.0 => 0.0,
+            DefinedFunction::Sign => argument.signum(),
+            _ => return None
+        };
+        if result.fract() == 0.0 { Some(result) } else { None }
+    }
+
+    //take the square factors out of the root of an integer times rest, sqrt(12 * x) is 2 * sqrt(3 * x)
+    fn split_sqrt(argument: f64, rest_factor: Expr) -> Expr {
+        let mut factor = 1.0;
+        let mut rest = argument;
+        let mut divisor = 2.0;
+        while argument.fract() == 0.0 && divisor * divisor <= rest {
+            if rest % (divisor * divisor) == 0.0 {
+                rest /= divisor * divisor;
+                factor *= divisor;
+            }
+            else {
+                divisor += 1.0;
+            }
+        }
+        Expr::Number(factor) * Expr::Call(DefinedFunction::Sqrt, vec![Expr::Number(rest) * rest_factor])
+    }
+
+    //flatten a sum into (coefficient, term) pairs, constants use the term 1
+    fn collect_terms(&self, sign: f64, terms: &mut Vec<(f64, Expr)>) {
+        match self {
+            Expr::Binary(TokenOperatorValue::Plus, left, right) => {
+                left.collect_terms(sign, terms);
+                right.collect_terms(sign, terms);
+            }
+            Expr::Binary(TokenOperatorValue::Minus, left, right) => {
+                left.collect_terms(sign, terms);
+                right.collect_terms(-sign, terms);
+            }
+            Expr::Negate(operand) => operand.collect_terms(-sign, terms),
+            Expr::Number(number) => Expr::add_term(terms, sign * number, Expr::Number(1.0)),
+            Expr::Binary(TokenOperatorValue::Multiply, left, right) => match left.as_ref() {
+                Expr::Number(number) => Expr::add_term(terms, sign * number, right.as_ref().clone()),
+                _ => Expr::add_term(terms, sign, self.clone()),
+            },
+            _ => Expr::add_term(terms, sign, self.clone()),
+        }
+    }
+
+    fn add_term(terms: &mut Vec<(f64, Expr)>, coefficient: f64, term: Expr) {
+        match terms.iter_mut().find(|(_, existing)| *existing == term) {
+            Some((existing, _)) => *existing += coefficient,
+            None => terms.push((coefficient, term)),
+        }
+    }
+
+    //highest degree first and constants last, like a polynomial is written
+    fn from_terms(mut terms: Vec<(f64, Expr)>) -> Expr {
+        terms.retain(|(coefficient, _)| *coefficient != 0.0);
+        terms.sort_by(|(_, a), (_, b)| b.degree().total_cmp(&a.degree()));
+
+        let mut result: Option<Expr> = None;
+        for (coefficient, term) in terms {
+            result = Some(match result {
+                None => Expr::Number(coefficient) * term,
+                Some(sum) if coefficient < 0.0 => sum - Expr::Number(-coefficient) * term,
+                Some(sum) => sum + Expr::Number(coefficient) * term,
+            });
+        }
+        result.unwrap_or(Expr::Number(0.0))
+    }
+
+    //flatten a product into a numeric coefficient and (base, exponent) pairs
+    fn collect_factors(&self, coefficient: &mut f64, factors: &mut Vec<(Expr, Expr)>) {
+        match self {
+            Expr::Binary(TokenOperatorValue::Multiply, left, right) => {
+                left.collect_factors(coefficient, factors);
+                right.collect_factors(coefficient, factors);
+            }
+            Expr::Binary(TokenOperatorValue::Divide, left, right) => match right.as_ref() {
+                Expr::Number(number) => {
+                    *coefficient /= number;
+                    left.collect_factors(coefficient, factors);
+                }
+                _ => Expr::add_factor(factors, self.clone(), Expr::Number(1.0)),
+            },
+            Expr::Number(number) => *coefficient *= number,
+            Expr::Negate(operand) => {
+                *coefficient = -*coefficient;
+                operand.collect_factors(coefficient, factors);
+            }
+            Expr::Binary(TokenOperatorValue::Power, base, exponent) => {
+                Expr::add_factor(factors, base.as_ref().clone(), exponent.as_ref().clone());
+            }
+            _ => Expr::add_factor(factors, self.clone(), Expr::Number(1.0)),
+        }
+    }
+
+    fn add_factor(factors: &mut Vec<(Expr, Expr)>, base: Expr, exponent: Expr) {
+        match factors.iter_mut().find(|(existing, _)| *existing == base) {
+            Some((_, existing)) => *existing = existing.clone() + exponent,
+            None => factors.push((base, exponent)),
+        }
+    }
+
+    fn from_factors(coefficient: f64, mut factors: Vec<(Expr, Expr)>) -> Expr {
+        //a canonical order lets x * y and y * x be collected as like terms
+        factors.sort_by_cached_key(|(base, _)| base.to_string());
+        let product = factors.into_iter()
+            .map(|(base, exponent)| Expr::pow(base, exponent))
+            .reduce(|product, factor| product * factor)
+            .unwrap_or(Expr::Number(1.0));
+        //x / 2 reads better than 0.5 * x
+        let inverse = 1.0 / coefficient;
+        if coefficient.fract() != 0.0 && inverse.fract() == 0.0 {
+            return (Expr::Number(coefficient.signum()) * product) / Expr::Number(inverse.abs());
+        }
+        Expr::Number(coefficient) * product
+    }
+
+    fn degree(&self) -> f64 {
+        match self {
+            Expr::Number(_) => 0.0,
+            Expr::Negate(operand) => operand.degree(),
+            Expr::Binary(TokenOperatorValue::Power, base, exponent) => match exponent.as_ref() {
+                Expr::Number(exponent) => exponent * base.degree(),
+                _ => 1.0,
+            },
+            Expr::Binary(TokenOperatorValue::Multiply, left, right) => left.degree() + right.degree(),
+            _ => 1.0,
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use crate::errors::{Error, ErrorType};
+use crate::lexer::tokens::binary_operator_token::TokenOperatorValue;
+use crate::lexer::tokens::function_token::DefinedFunction;
+use crate::symbolic::Expr;
+
+//integer powers expanded when reading a polynomial, higher ones are solved numerically
+const MAX_EXPANDED_POWER: f64 = 16.0;
+//numeric roots are searched by sign changes on this grid of [-SEARCH_RANGE, SEARCH_RANGE]
+const SEARCH_RANGE: f64 = 100.0;
+const SEARCH_STEPS: usize = 4000;
+//without a root there the bracket grows geometrically by SEARCH_GROWTH up to MAX_SEARCH_RANGE
+const SEARCH_GROWTH: f64 = 1.05;
+const MAX_SEARCH_RANGE: f64 = 1e15;
+//a periodic equation is solved on one period, (-pi, pi] when it is 2 * pi, sin(k * x) with k = p / q has one of 2 * pi * q
+const MAX_PERIOD_DENOMINATOR: u64 = 12;
+
+impl Expr {
+    //real solutions of self = 0, exact up to degree 2 and numeric otherwise
+    pub fn solve(&self, variable: &str) -> Result<Vec<Expr>, ErrorType> {
+        let equation = self.simplify();
+        match equation.polynomial(variable) {
+            Some(coefficients) if coefficients.len() <= 3 => Expr::solve_polynomial(coefficients),
+            _ => Expr::solve_numeric(&equation, variable),
+        }
+    }
+
+    //coefficients of the polynomial in variable from the constant one up, None if it is not one
+    fn polynomial(&self, variable: &str) -> Option<Vec<Expr>> {
+        if !self.contains(variable) {
+            return Some(vec![self.clone()]);
+        }
+        let coefficients = match self {
+            Expr::Variable(_) => vec![Expr::Number(0.0), Expr::Number(1.0)],
+            Expr::Negate(operand) => operand.polynomial(variable)?.into_iter().map(|c| -c).collect(),
+            Expr::Binary(operator, left, right) => {
+                match operator {
+                    TokenOperatorValue::Plus => Expr::add_polynomials(left.polynomial(variable)?, right.polynomial(variable)?, 1.0),
+                    TokenOperatorValue::Minus => Expr::add_polynomials(left.polynomial(variable)?, right.polynomial(variable)?, -1.0),
+                    TokenOperatorValue::Multiply => Expr::multiply_polynomials(&left.polynomial(variable)?, &right.polynomial(variable)?),
+                    TokenOperatorValue::Divide if !right.contains(variable) => {
+                        left.polynomial(variable)?.into_iter().map(|c| c / right.as_ref().clone()).collect()
+                    }
+                    TokenOperatorValue::Power => match right.as_ref() {
+                        Expr::Number(exponent) if exponent.fract() == 0.0 && (0.0..=MAX_EXPANDED_POWER).contains(exponent) => {
+                            let base = left.polynomial(variable)?;
+                            (0..*exponent as usize).fold(vec![Expr::Number(1.0)], |power, _| Expr::multiply_polynomials(&power, &base))
+                        }
+                        _ => return None
+                    },
+                    _ => return None
+                }
+            }
+            _ => return None
+        };
+        let mut coefficients: Vec<Expr> = coefficients.iter().map(Expr::simplify).collect();
+        while coefficients.len() > 1 && coefficients.last() == Some(&Expr::Number(0.0)) {
+            coefficients.pop();
+        }
+        Some(coefficients)
+    }
+
+    fn add_polynomials(left: Vec<Expr>, right: Vec<Expr>, sign: f64) -> Vec<Expr> {
+        let length = left.len().max(right.len());
+        let coefficient = |p: &Vec<Expr>, i: usize| p.get(i).cloned().unwrap_or(Expr::Number(0.0));
+        (0..length).map(|i| coefficient(&left, i) + Expr::Number(sign) * coefficient(&right, i)).collect()
+    }
+
+    fn multiply_polynomials(left: &[Expr], right: &[Expr]) -> Vec<Expr> {
+        let mut product = vec![Expr::Number(0.0); left.len() + right.len() - 1];
+        for (i, a) in left.iter().enumerate() {
+            for (j, b) in right.iter().enumerate() {
+                product[i + j] = product[i + j].clone() + a.clone() * b.clone();
+            }
+        }
+        product
+    }
+
+    //constant, linear and quadratic equations, an equation without a finite set of solutions is an InvalidOperation
+    fn solve_polynomial(coefficients: Vec<Expr>) -> Result<Vec<Expr>, ErrorType> {
+        Ok(match coefficients.as_slice() {
+            [b, a] => vec![(-b.clone() / a.clone()).simplify()],
+            [c, b, a] => {
+                let discriminant = (b.clone() * b.clone() - Expr::Number(4.0) * a.clone() * c.clone()).simplify();
+                let denominator = Expr::Number(2.0) * a.clone();
+                match discriminant {
+                    Expr::Number(d) if d < 0.0 => return Error::throw(ErrorType::InvalidOperation),
+                    Expr::Number(0.0) => vec![(-b.clone() / denominator).simplify()],
+                    discriminant => {
+                        let root = Expr::Call(DefinedFunction::Sqrt, vec![discriminant]);
+                        let mut roots = vec![
+                            ((-b.clone() - root.clone()) / denominator.clone()).simplify(),
+                            ((-b.clone() + root) / denominator).simplify(),
+                        ];
+                        //smallest first when the parabola opens downwards
+                        if matches!(a, Expr::Number(a) if *a < 0.0) {
+                            roots.reverse();
+                        }
+                        roots
+                    }
+                }
+            }
+            _ => return Error::throw(ErrorType::InvalidOperation)
+        })
+    }
+
+    //roots where the sign changes on the grid of [-SEARCH_RANGE, SEARCH_RANGE], refined with Brent's method, see Expr::find_root
+    //without one there the bracket grows geometrically on both sides, an InvalidOperation once past MAX_SEARCH_RANGE
+    //a periodic equation only gives its roots on one period, the others are these plus multiples of it
+    fn solve_numeric(equation: &Expr, variable: &str) -> Result<Vec<Expr>, ErrorType> {
+        equation.require_bound(Some(variable))?;
+        let f = |x: f64| equation.evaluate(variable, x);
+        let period = equation.period(variable);
+        let range = period.map_or(SEARCH_RANGE, |period| std::f64::consts::PI * period as f64);
+        let step = 2.0 * range / SEARCH_STEPS as f64;
+        let mut roots = Expr::sign_changes(&f, (0..=SEARCH_STEPS).map(|i| -range + step * i as f64));
+        //the ends of a period are the same root, which is kept at the right end, found by a step past it
+        if period.is_some() {
+            roots.extend(Expr::sign_changes(&f, [range - step, range + step].into_iter()));
+            roots.retain(|root| root + range > 1e-9 * (1.0 + range) && root - range <= 1e-9 * (1.0 + range));
+            roots.dedup_by(|a, b| (*a - *b).abs() <= 1e-9 * (1.0 + b.abs()));
+        }
+        if roots.is_empty() && period.is_none() {
+            let outer: Vec<f64> = std::iter::successors(Some(SEARCH_RANGE), |x| Some(x * SEARCH_GROWTH))
+                .take_while(|x| *x <= MAX_SEARCH_RANGE)
+                .collect();
+            roots = Expr::sign_changes(&f, outer.iter().rev().map(|x| -x));
+            roots.reverse();
+            roots.extend(Expr::sign_changes(&f, outer.iter().copied()));
+        }
+        if roots.is_empty() {
+            return Error::throw(ErrorType::InvalidOperation);
+        }
+        Ok(roots.into_iter().map(Expr::Number).collect())
+    }
+
+    //variable only appears in sin and cos of a rational multiple of it plus a constant, self then repeats every
+    //2 * pi times the returned number
+    fn period(&self, variable: &str) -> Option<u64> {
+        let lcm = |a: u64, b: u64| (1..=b).map(|n| a * n).find(|multiple| multiple % b == 0).unwrap_or(a * b);
+        match self {
+            Expr::Number(_) => Some(1),
+            Expr::Variable(name) => (name != variable).then_some(1),
+            Expr::Negate(operand) => operand.period(variable),
+            Expr::Binary(_, left, right) => Some(lcm(left.period(variable)?, right.period(variable)?)),
+            Expr::Call(DefinedFunction::Sin | DefinedFunction::Cos, args) if args.iter().any(|arg| arg.contains(variable)) => {
+                let [argument] = args.as_slice() else { return None };
+                match argument.polynomial(variable)?.as_slice() {
+                    [_, Expr::Number(multiple)] => {
+                        (1..=MAX_PERIOD_DENOMINATOR).find(|denominator| (multiple * *denominator as f64).fract() == 0.0)
+                    }
+                    _ => None
+                }
+            }
+            Expr::Call(_, args) => args.iter().try_fold(1, |period, arg| Some(lcm(period, arg.period(variable)?))),
+        }
+    }
+
+    //roots of f between consecutive points, in the order of the points
+    fn sign_changes(f: &dyn Fn(f64) -> f64, points: impl Iterator<Item = f64>) -> Vec<f64> {
+        let values: Vec<(f64, f64)> = points.map(|x| (x, f(x))).collect();
+        let mut roots: Vec<f64> = Vec::new();
+        for (i, &(x, fx)) in values.iter().enumerate() {
+            let previous = i.checked_sub(1).map(|i| values[i]);
+            //a run of zeros is an underflow like exp(x) far below zero rather than roots
+            let root = if fx == 0.0 {
+                let isolated = [previous, values.get(i + 1).copied()].iter().flatten().all(|(_, fy)| *fy != 0.0);
+                isolated.then_some(x)
+            }
+            else {
+                previous.filter(|(_, fp)| fp * fx < 0.0).and_then(|(p, _)| Expr::find_root(f, p, x))
+            };
+            if let Some(root) = root {
+                let root = Expr::round_off(root);
+                if roots.last().is_none_or(|last| (root - last).abs() > 1e-9 * (1.0 + root.abs())) {
+                    roots.push(root);
+                }
+            }
+        }
+        roots
+    }
+}
@@ -0,0 +1,164 @@
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, Mul, Neg, Sub};
+use crate::errors::{Error, ErrorType};
+use crate::symbolic::Expr;
+use crate::value::Value;
+
+//parts smaller than this relative to the modulus are rounding errors, like the real part of exp(i * pi / 2)
+const NOISE: f64 = 1e-12;
+//integer powers up to this one are computed by repeated products, which keeps them exact
+const MAX_EXACT_POWER: f64 = 64.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Complex {
+    pub re: f64,
+    pub im: f64,
+}
+
+impl Complex {
+    pub const I: Complex = Complex { re: 0.0, im: 1.0 };
+
+    pub fn new(re: f64, im: f64) -> Complex {
+        Complex { re, im }
+    }
+
+    pub fn from_polar(modulus: f64, argument: f64) -> Complex {
+        Complex::new(modulus * argument.cos(), modulus * argument.sin())
+    }
+
+    pub fn abs(&self) -> f64 {
+        self.re.hypot(self.im)
+    }
+
+    //principal argument, in (-pi, pi]
+    pub fn arg(&self) -> f64 {
+        self.im.atan2(self.re)
+    }
+
+    pub fn conj(&self) -> Complex {
+        Complex::new(self.re, -self.im)
+    }
+
+    //principal square root, its real part is never negative
+    pub fn sqrt(&self) -> Complex {
+        let modulus = self.abs();
+        let re = ((modulus + self.re) / 2.0).sqrt();
+        let im = ((modulus - self.re) / 2.0).sqrt();
+        Complex::new(re, if self.im < 0.0 { -im } else { im })
+    }
+
+    pub fn exp(&self) -> Complex {
+        Complex::from_polar(self.re.exp(), self.im)
+    }
+
+    //principal logarithm, ln(0) is an InvalidOperation
+    pub fn ln(&self) -> Result<Complex, ErrorType> {
+        if self.re == 0.0 && self.im == 0.0 {
+            return Error::throw(ErrorType::InvalidOperation);
+        }
+        Ok(Complex::new(self.abs().ln(), self.arg()))
+    }
+
+    pub fn sin(&self) -> Complex {
+        Complex::new(self.re.sin() * self.im.cosh(), self.re.cos() * self.im.sinh())
+    }
+
+    pub fn cos(&self) -> Complex {
+        Complex::new(self.re.cos() * self.im.cosh(), -self.re.sin() * self.im.sinh())
+    }
+
+    //principal power exp(exponent * ln(self))
+    pub fn pow(&self, exponent: Complex) -> Result<Complex, ErrorType> {
+        if exponent.im == 0.0 && exponent.re.fract() == 0.0 && exponent.re.abs() <= MAX_EXACT_POWER {
+            return self.powi(exponent.re as i64);
+        }
+        if self.re == 0.0 && self.im == 0.0 {
+            if exponent.re <= 0.0 {
+                return Error::throw(ErrorType::InvalidOperation);
+            }
+            return Ok(Complex::new(0.0, 0.0));
+        }
+        Ok((exponent * self.ln()?).exp())
+    }
+
+    //a division by zero is an InvalidOperation like the one of integers
+    pub fn try_div(&self, other: &Complex) -> Result<Complex, ErrorType> {
+        let denominator = other.re * other.re + other.im * other.im;
+        if denominator == 0.0 {
+            return Error::throw(ErrorType::InvalidOperation);
+        }
+        Ok(Complex::new(
+            (self.re * other.re + self.im * other.im) / denominator,
+            (self.im * other.re - self.re * other.im) / denominator,
+        ))
+    }
+
+    fn powi(&self, exponent: i64) -> Result<Complex, ErrorType> {
+        let mut base = if exponent < 0 { Complex::new(1.0, 0.0).try_div(self)? } else { *self };
+        let mut exponent = exponent.unsigned_abs();
+        let mut result = Complex::new(1.0, 0.0);
+        while exponent > 0 {
+            if exponent % 2 == 1 {
+                result = result * base;
+            }
+            base = base * base;
+            exponent /= 2;
+        }
+        Ok(result)
+    }
+
+    //rounding errors dropped, see NOISE and Expr::round_off
+    pub fn clean(&self) -> Complex {
+        let noise = NOISE * self.abs();
+        let part = |part: f64| if part.abs() < noise { 0.0 } else { Expr::round_off(part) };
+        Complex::new(part(self.re), part(self.im))
+    }
+}
+
+impl Add for Complex {
+    type Output = Complex;
+
+    fn add(self, other: Complex) -> Complex {
+        Complex::new(self.re + other.re, self.im + other.im)
+    }
+}
+
+impl Sub for Complex {
+    type Output = Complex;
+
+    fn sub(self, other: Complex) -> Complex {
+        Complex::new(self.re - other.re, self.im - other.im)
+    }
+}
+
+impl Mul for Complex {
+    type Output = Complex;
+
+    fn mul(self, other: Complex) -> Complex {
+        Complex::new(self.re * other.re - self.im * other.im, self.re * other.im + self.im * other.re)
+    }
+}
+
+impl Neg for Complex {
+    type Output = Complex;
+
+    fn neg(self) -> Complex {
+        Complex::new(-self.re, -self.im)
+    }
+}
+
+//a + bi, without the real part when it is zero and without the coefficient of a unit imaginary part
+impl Display for Complex {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let imaginary = match self.im.abs() {
+            1.0 => "i".to_string(),
+            im => format!("{}i", Value::from_number(im)),
+        };
+        match (self.re, self.im < 0.0) {
+            (0.0, true) => write!(f, "-{}", imaginary),
+            (0.0, false) => write!(f, "{}", imaginary),
+            (re, true) => write!(f, "{} - {}", Value::from_number(re), imaginary),
+            (re, false) => write!(f, "{} + {}", Value::from_number(re), imaginary),
+        }
+    }
+}
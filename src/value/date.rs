@@ -0,0 +1,108 @@
+use std::fmt::{Display, Formatter};
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::errors::{Error, ErrorType};
+
+const SECONDS_PER_DAY: i64 = 86_400;
+//dates are kept within about 300000 years of 1970
+const MAX_SECONDS: f64 = 1e13;
+
+//instant in UTC to the second, counted from 1970-01-01
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Date {
+    seconds: i64,
+}
+
+impl Date {
+    //2026-10-18 or 2026-10-18T09:30 with optional seconds, None for a day or a time which does not exist
+    pub fn parse(text: &str) -> Option<Date> {
+        let (date, time) = text.split_once('T').unwrap_or((text, "00:00"));
+        let number = |part: Option<&str>, digits: usize| match part {
+            Some(part) if part.len() == digits && part.chars().all(|c| c.is_ascii_digit()) => part.parse::<i64>().ok(),
+            _ => None
+        };
+        let mut parts = date.split('-');
+        let (year, month, day) = (number(parts.next(), 4)?, number(parts.next(), 2)?, number(parts.next(), 2)?);
+        let mut parts = time.split(':');
+        let (hour, minute) = (number(parts.next(), 2)?, number(parts.next(), 2)?);
+        let second = match parts.next() {
+            Some(part) => number(Some(part), 2)?,
+            None => 0
+        };
+        let days = Date::days_from_civil(year, month, day);
+        if parts.next().is_some() || Date::civil_from_days(days) != (year, month, day) || hour > 23 || minute > 59 || second > 59 {
+            return None;
+        }
+        Some(Date { seconds: days * SECONDS_PER_DAY + hour * 3600 + minute * 60 + second })
+    }
+
+    pub fn now() -> Date {
+        let seconds = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs() as i64);
+        Date { seconds }
+    }
+
+    //date moved by a number of seconds, rounded to the second
+    pub fn add_seconds(&self, seconds: f64) -> Result<Date, ErrorType> {
+        let seconds = self.seconds as f64 + seconds.round();
+        if seconds.is_nan() || seconds.abs() > MAX_SECONDS {
+            return Error::throw(ErrorType::InvalidOperation);
+        }
+        Ok(Date { seconds: seconds as i64 })
+    }
+
+    //seconds from other to self, negative when other is later
+    pub fn seconds_since(&self, other: &Date) -> f64 {
+        (self.seconds - other.seconds) as f64
+    }
+
+    //ISO day of the week, 1 for monday to 7 for sunday
+    pub fn weekday(&self) -> i64 {
+        //1970-01-01 was a thursday
+        (self.days() + 3).rem_euclid(7) + 1
+    }
+
+    //ISO week number, week 1 is the one with the first thursday of the year
+    pub fn iso_week(&self) -> i64 {
+        let thursday = self.days() - self.weekday() + 4;
+        let (year, _, _) = Date::civil_from_days(thursday);
+        (thursday - Date::days_from_civil(year, 1, 1)) / 7 + 1
+    }
+
+    fn days(&self) -> i64 {
+        self.seconds.div_euclid(SECONDS_PER_DAY)
+    }
+
+    //days since 1970-01-01 of a day of the proleptic Gregorian calendar, see http://howardhinnant.github.io/date_algorithms.html
+    fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+        let year = if month <= 2 { year - 1 } else { year };
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    fn civil_from_days(days: i64) -> (i64, i64, i64) {
+        let days = days + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days - era * 146_097;
+        let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+        let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+        (year_of_era + era * 400 + if month <= 2 { 1 } else { 0 }, month, day)
+    }
+}
+
+//the time is written only when there is one, the same way dates are typed
+impl Display for Date {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let (year, month, day) = Date::civil_from_days(self.days());
+        write!(f, "{:04}-{:02}-{:02}", year, month, day)?;
+        let time = self.seconds.rem_euclid(SECONDS_PER_DAY);
+        if time != 0 {
+            write!(f, "T{:02}:{:02}:{:02}", time / 3600, time / 60 % 60, time % 60)?;
+        }
+        Ok(())
+    }
+}
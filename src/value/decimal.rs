@@ -0,0 +1,399 @@
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::ops::Neg;
+use crate::errors::{Error, ErrorType};
+use crate::value::natural::Natural;
+use crate::value::rational::Rational;
+
+//without a precision, fractional digits kept by a product, the last ones are rounded off past this
+const MAX_SCALE: u32 = 28;
+//without a precision, fractional digits of a quotient which does not end
+const DIVISION_SCALE: u32 = 16;
+//extra digits exp, ln and the operations before a rounded result work with so it is right once rounded
+const GUARD_DIGITS: u32 = 10;
+//exp of larger numbers has too many digits to be worth computing
+const MAX_EXP_ARGUMENT: f64 = 10_000.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Rounding {
+    //ties to the even neighbour, the default
+    HalfEven,
+    //ties away from zero
+    HalfUp,
+    Floor,
+    Ceil,
+}
+
+impl Rounding {
+    pub fn parse(name: &str) -> Option<Rounding> {
+        match name {
+            "half-even" => Some(Rounding::HalfEven),
+            "half-up" => Some(Rounding::HalfUp),
+            "floor" => Some(Rounding::Floor),
+            "ceil" => Some(Rounding::Ceil),
+            _ => None
+        }
+    }
+}
+
+//number of fractional digits every decimal result is rounded to, and how
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Precision {
+    pub digits: u32,
+    pub rounding: Rounding,
+}
+
+impl Precision {
+    //precision of the operations before a result, with guard digits so only the result is rounded the way asked
+    pub fn working(self) -> Precision {
+        Precision { digits: self.digits + GUARD_DIGITS, rounding: Rounding::HalfEven }
+    }
+}
+
+//digits / 10^scale with a sign, exact whatever the number of digits
+#[derive(Debug, Clone, PartialEq)]
+pub struct Decimal {
+    negative: bool,
+    digits: Natural,
+    scale: u32,
+}
+
+impl Decimal {
+    //number literal such as 12.50, which keeps its two fractional digits
+    pub fn parse(text: &str) -> Option<Decimal> {
+        let (negative, text) = match text.strip_prefix('-') {
+            Some(text) => (true, text),
+            None => (false, text)
+        };
+        let (integer, fraction) = text.split_once('.').unwrap_or((text, ""));
+        let digits = Natural::parse(&format!("{}{}", integer, fraction))?;
+        Some(Decimal::new(negative, digits, fraction.len() as u32))
+    }
+
+    pub fn from_integer(number: i64) -> Decimal {
+        Decimal::new(number < 0, Natural::from_u64(number.unsigned_abs()), 0)
+    }
+
+    //shortest decimal of a floating point number, None for infinities and NaN
+    pub fn from_f64(number: f64) -> Option<Decimal> {
+        if number.is_finite() { Decimal::parse(&number.to_string()) } else { None }
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        self.to_string().parse().unwrap_or(f64::NAN)
+    }
+
+    //value of a decimal without fractional part, None past the i64 range
+    pub fn to_integer(&self) -> Option<i64> {
+        let (integer, fraction) = self.digits.div_rem(&Natural::power_of_ten(self.scale));
+        if !fraction.is_zero() {
+            return None;
+        }
+        let magnitude = i64::try_from(integer.to_u64()?).ok()?;
+        Some(if self.negative { -magnitude } else { magnitude })
+    }
+
+    //exact fraction of a decimal, None when its digits or its denominator are past the i64 range
+    pub fn to_rational(&self) -> Option<Rational> {
+        let numerator = i64::try_from(self.digits.to_u64()?).ok()?;
+        let denominator = i64::try_from(Natural::power_of_ten(self.scale).to_u64()?).ok()?;
+        Rational::new(if self.negative { -numerator } else { numerator }, denominator)
+    }
+
+    pub fn abs(&self) -> Decimal {
+        Decimal::new(false, self.digits.clone(), self.scale)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.digits.is_zero()
+    }
+
+    pub fn compare(&self, other: &Decimal) -> Ordering {
+        let difference = self.sub(other);
+        if difference.is_zero() {
+            Ordering::Equal
+        }
+        else if difference.negative {
+            Ordering::Less
+        }
+        else {
+            Ordering::Greater
+        }
+    }
+
+    pub fn add(&self, other: &Decimal) -> Decimal {
+        let scale = self.scale.max(other.scale);
+        let (a, b) = (self.digits_at(scale), other.digits_at(scale));
+        if self.negative == other.negative {
+            return Decimal::new(self.negative, a.add(&b), scale);
+        }
+        match a.cmp(&b) {
+            Ordering::Less => Decimal::new(other.negative, b.sub(&a), scale),
+            _ => Decimal::new(self.negative, a.sub(&b), scale),
+        }
+    }
+
+    pub fn sub(&self, other: &Decimal) -> Decimal {
+        self.add(&-other.clone())
+    }
+
+    pub fn mul(&self, other: &Decimal) -> Decimal {
+        Decimal::new(self.negative != other.negative, self.digits.mul(&other.digits), self.scale + other.scale)
+    }
+
+    //quotient with scale fractional digits, other is not zero
+    fn div(&self, other: &Decimal, scale: u32, rounding: Rounding) -> Decimal {
+        let exponent = scale as i64 + other.scale as i64 - self.scale as i64;
+        let (numerator, divisor) = if exponent >= 0 {
+            (self.digits.mul(&Natural::power_of_ten(exponent as u32)), other.digits.clone())
+        }
+        else {
+            (self.digits.clone(), other.digits.mul(&Natural::power_of_ten((-exponent) as u32)))
+        };
+        let negative = self.negative != other.negative;
+        let (quotient, remainder) = numerator.div_rem(&divisor);
+        Decimal::new(negative, Decimal::rounded(negative, quotient, &remainder, &divisor, rounding), scale)
+    }
+
+    //remainder of the division truncated towards zero, it has the sign of self
+    pub fn rem(&self, other: &Decimal) -> Result<Decimal, ErrorType> {
+        if other.is_zero() {
+            return Error::throw(ErrorType::InvalidOperation);
+        }
+        let scale = self.scale.max(other.scale);
+        Ok(Decimal::new(self.negative, self.digits_at(scale).div_rem(&other.digits_at(scale)).1, scale))
+    }
+
+    //exact for a natural exponent
+    pub fn powi(&self, exponent: u64) -> Decimal {
+        let mut base = self.clone();
+        let mut power = Decimal::from_integer(1);
+        let mut remaining = exponent;
+        while remaining > 0 {
+            if remaining % 2 == 1 {
+                power = power.mul(&base);
+            }
+            remaining /= 2;
+            if remaining > 0 {
+                base = base.mul(&base);
+            }
+        }
+        power
+    }
+
+    //number with exactly scale fractional digits
+    pub fn round(&self, scale: u32, rounding: Rounding) -> Decimal {
+        if scale >= self.scale {
+            return Decimal::new(self.negative, self.digits_at(scale), scale);
+        }
+        let divisor = Natural::power_of_ten(self.scale - scale);
+        let (quotient, remainder) = self.digits.div_rem(&divisor);
+        Decimal::new(self.negative, Decimal::rounded(self.negative, quotient, &remainder, &divisor, rounding), scale)
+    }
+
+    //result of an operation rounded to the precision, without one only products past MAX_SCALE digits are
+    //those keep MAX_SCALE significant digits so a small product is not shown as 0, and the cut zeros are dropped
+    pub fn fit(self, precision: Option<Precision>) -> Decimal {
+        match precision {
+            Some(precision) => self.round(precision.digits, precision.rounding),
+            None if self.scale > MAX_SCALE => {
+                let leading_zeros = self.scale.saturating_sub(self.digits.to_string().len() as u32);
+                self.round(MAX_SCALE + leading_zeros, Rounding::HalfUp).trim(0)
+            }
+            None => self
+        }
+    }
+
+    //quotient at the precision, without one it is exact when it ends and has DIVISION_SCALE digits otherwise
+    //a division by zero is an InvalidOperation
+    pub fn quotient(&self, other: &Decimal, precision: Option<Precision>) -> Result<Decimal, ErrorType> {
+        if other.is_zero() {
+            return Error::throw(ErrorType::InvalidOperation);
+        }
+        Ok(match precision {
+            Some(precision) => self.div(other, precision.digits, precision.rounding),
+            None => {
+                let scale = self.scale.max(other.scale);
+                self.div(other, scale.max(DIVISION_SCALE), Rounding::HalfUp).trim(scale)
+            }
+        })
+    }
+
+    //self^exponent as exp(exponent * ln(self)) with scale fractional digits, self is positive
+    pub fn power_real(&self, exponent: &Decimal, scale: u32, rounding: Rounding) -> Result<Decimal, ErrorType> {
+        let working = scale + GUARD_DIGITS;
+        exponent.mul(&self.ln(working, Rounding::HalfEven)?).exp(scale, rounding)
+    }
+
+    //quotient with scale fractional digits, a division by zero is an InvalidOperation
+    pub fn divide(&self, other: &Decimal, scale: u32, rounding: Rounding) -> Result<Decimal, ErrorType> {
+        if other.is_zero() {
+            return Error::throw(ErrorType::InvalidOperation);
+        }
+        Ok(self.div(other, scale, rounding))
+    }
+
+    //a result computed with more digits than it has, at the precision or with DIVISION_SCALE digits like a quotient
+    pub fn approximate(self, precision: Option<Precision>) -> Decimal {
+        match precision {
+            Some(precision) => self.round(precision.digits, precision.rounding),
+            None => self.round(self.scale.min(DIVISION_SCALE), Rounding::HalfUp).trim(0)
+        }
+    }
+
+    //integer power, a negative exponent is the quotient of one by the power
+    pub fn power(&self, exponent: i64, precision: Option<Precision>) -> Result<Decimal, ErrorType> {
+        let power = self.powi(exponent.unsigned_abs());
+        if exponent < 0 {
+            Decimal::from_integer(1).quotient(&power, precision)
+        }
+        else {
+            Ok(power.fit(precision))
+        }
+    }
+
+    //square root correctly rounded to scale digits, a negative number is an InvalidOperation
+    pub fn sqrt(&self, scale: u32, rounding: Rounding) -> Result<Decimal, ErrorType> {
+        if self.negative {
+            return Error::throw(ErrorType::InvalidOperation);
+        }
+        //the root of digits * 10^(2 * working - self.scale) has working fractional digits
+        let working = scale.max(self.scale.div_ceil(2));
+        let radicand = self.digits_at(2 * working);
+        let root = radicand.sqrt();
+        let remainder = radicand.sub(&root.mul(&root));
+        //the root is above root + 1/2 exactly when the remainder is above root
+        let up = match rounding {
+            Rounding::Floor => false,
+            Rounding::Ceil => !remainder.is_zero(),
+            Rounding::HalfEven | Rounding::HalfUp => remainder > root,
+        };
+        let root = if up { root.add(&Natural::from_u64(1)) } else { root };
+        Ok(Decimal::new(false, root, working).round(scale, rounding))
+    }
+
+    //e^self from its Taylor series, after halving self until it is small and squaring back as many times
+    pub fn exp(&self, scale: u32, rounding: Rounding) -> Result<Decimal, ErrorType> {
+        let magnitude = self.to_f64().abs();
+        //far below the last digit of the scale, only the rounding decides between 0 and that digit
+        if self.negative && magnitude * std::f64::consts::LOG10_E > (scale + 2) as f64 {
+            return Ok(Decimal::new(false, Natural::from_u64(1), scale + 2).round(scale, rounding));
+        }
+        if magnitude > MAX_EXP_ARGUMENT {
+            return Error::throw(ErrorType::InvalidOperation);
+        }
+        let halvings = (magnitude.max(1.0).log2().ceil() as u32) + 1;
+        //digits lost in the squares and in the integer part of the result
+        let working = scale + GUARD_DIGITS + halvings + (magnitude * std::f64::consts::LOG10_E).ceil() as u32;
+        let reduced = self.abs().div(&Decimal::from_integer(2).powi(halvings as u64), working, Rounding::HalfEven);
+        let mut sum = Decimal::from_integer(1);
+        let mut term = Decimal::from_integer(1);
+        let mut n = 1;
+        while !term.is_zero() {
+            term = term.mul(&reduced).div(&Decimal::from_integer(n), working, Rounding::HalfEven);
+            sum = sum.add(&term);
+            n += 1;
+        }
+        for _ in 0..halvings {
+            sum = sum.mul(&sum).round(working, Rounding::HalfEven);
+        }
+        if self.negative {
+            sum = Decimal::from_integer(1).div(&sum, working, Rounding::HalfEven);
+        }
+        Ok(sum.round(scale, rounding))
+    }
+
+    //natural logarithm as k * ln(2) + 2 * atanh((y - 1) / (y + 1)) where self = y * 2^k with y near 1
+    //a number which is not positive is an InvalidOperation
+    pub fn ln(&self, scale: u32, rounding: Rounding) -> Result<Decimal, ErrorType> {
+        if self.negative || self.is_zero() {
+            return Error::throw(ErrorType::InvalidOperation);
+        }
+        let digits = self.digits.to_string().len() as f64 - self.scale as f64;
+        let exponent = match self.to_f64() {
+            number if number.is_normal() => number.log2().round() as i64,
+            _ => (digits * std::f64::consts::LOG2_10).round() as i64,
+        };
+        let working = scale + GUARD_DIGITS + exponent.unsigned_abs().to_string().len() as u32;
+        let two = Decimal::from_integer(2).powi(exponent.unsigned_abs());
+        let reduced = if exponent >= 0 { self.div(&two, working, Rounding::HalfEven) } else { self.mul(&two) };
+        let one = Decimal::from_integer(1);
+        let ratio = reduced.sub(&one).div(&reduced.add(&one), working, Rounding::HalfEven);
+        let ln2 = Decimal::atanh(&one.div(&Decimal::from_integer(3), working, Rounding::HalfEven), working);
+        let sum = Decimal::from_integer(exponent).mul(&ln2).add(&Decimal::atanh(&ratio, working));
+        Ok(sum.mul(&Decimal::from_integer(2)).round(scale, rounding))
+    }
+
+    //series of atanh for |x| well below 1
+    fn atanh(x: &Decimal, working: u32) -> Decimal {
+        let square = x.mul(x).round(working, Rounding::HalfEven);
+        let mut power = x.clone();
+        let mut sum = x.clone();
+        let mut n = 3;
+        while !power.is_zero() {
+            power = power.mul(&square).round(working, Rounding::HalfEven);
+            sum = sum.add(&power.div(&Decimal::from_integer(n), working, Rounding::HalfEven));
+            n += 2;
+        }
+        sum
+    }
+
+    fn new(negative: bool, digits: Natural, scale: u32) -> Decimal {
+        Decimal { negative: negative && !digits.is_zero(), digits, scale }
+    }
+
+    //digits of self written with scale fractional digits, scale is at least self.scale
+    fn digits_at(&self, scale: u32) -> Natural {
+        self.digits.mul(&Natural::power_of_ten(scale - self.scale))
+    }
+
+    //trailing zeros dropped down to min_scale fractional digits
+    fn trim(self, min_scale: u32) -> Decimal {
+        let mut trimmed = self;
+        while trimmed.scale > min_scale {
+            let (quotient, remainder) = trimmed.digits.div_rem(&Natural::from_u64(10));
+            if !remainder.is_zero() {
+                break;
+            }
+            trimmed = Decimal::new(trimmed.negative, quotient, trimmed.scale - 1);
+        }
+        trimmed
+    }
+
+    //quotient of a division truncated towards zero, moved one unit away from zero when the rounding asks for it
+    fn rounded(negative: bool, quotient: Natural, remainder: &Natural, divisor: &Natural, rounding: Rounding) -> Natural {
+        let exact = remainder.is_zero();
+        let half = remainder.add(remainder).cmp(divisor);
+        let up = match rounding {
+            Rounding::Floor => negative && !exact,
+            Rounding::Ceil => !negative && !exact,
+            Rounding::HalfUp => half != Ordering::Less,
+            Rounding::HalfEven => half == Ordering::Greater || (half == Ordering::Equal && !quotient.is_even()),
+        };
+        if up { quotient.add(&Natural::from_u64(1)) } else { quotient }
+    }
+}
+
+impl Neg for Decimal {
+    type Output = Decimal;
+
+    fn neg(self) -> Decimal {
+        Decimal::new(!self.negative, self.digits, self.scale)
+    }
+}
+
+impl Display for Decimal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let digits = format!("{:0>width$}", self.digits.to_string(), width = self.scale as usize + 1);
+        let (integer, fraction) = digits.split_at(digits.len() - self.scale as usize);
+        if self.negative {
+            write!(f, "-")?;
+        }
+        if fraction.is_empty() {
+            write!(f, "{}", integer)
+        }
+        else {
+            write!(f, "{}.{}", integer, fraction)
+        }
+    }
+}
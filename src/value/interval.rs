@@ -0,0 +1,266 @@
+use std::fmt::{Display, Formatter};
+use std::ops::Neg;
+use crate::errors::{Error, ErrorType};
+
+//every real number between lo and hi, the bounds of an inexact result are rounded outwards
+//so the interval still holds the exact result despite floating point rounding
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Interval {
+    pub lo: f64,
+    pub hi: f64,
+}
+
+impl Interval {
+    //an InvalidOperation unless lo <= hi
+    pub fn new(lo: f64, hi: f64) -> Result<Interval, ErrorType> {
+        if lo.is_nan() || hi.is_nan() || lo > hi {
+            return Error::throw(ErrorType::InvalidOperation);
+        }
+        Ok(Interval { lo, hi })
+    }
+
+    pub fn point(number: f64) -> Interval {
+        Interval { lo: number, hi: number }
+    }
+
+    //center ± radius
+    pub fn around(center: f64, radius: f64) -> Result<Interval, ErrorType> {
+        if radius < 0.0 {
+            return Error::throw(ErrorType::InvalidOperation);
+        }
+        Interval::new(add_down(center, -radius), add_up(center, radius))
+    }
+
+    //center and radius of an interval which holds this one
+    pub fn center_radius(&self) -> (f64, f64) {
+        let center = self.lo / 2.0 + self.hi / 2.0;
+        (center, add_up(self.hi, -center).max(add_up(center, -self.lo)))
+    }
+
+    pub fn contains(&self, number: f64) -> bool {
+        self.lo <= number && number <= self.hi
+    }
+
+    //single number, Some for an interval of width zero
+    pub fn to_point(&self) -> Option<f64> {
+        if self.lo == self.hi { Some(self.lo) } else { None }
+    }
+
+    //image by an increasing function, an InvalidOperation outside its domain
+    pub fn map_increasing(&self, function: impl Fn(f64) -> f64) -> Result<Interval, ErrorType> {
+        Interval::outward(function(self.lo), function(self.hi))
+    }
+
+    //a square root is correctly rounded, it is exact when its square is
+    pub fn sqrt(&self) -> Result<Interval, ErrorType> {
+        let root = |number: f64, up: bool| {
+            let root = number.sqrt();
+            let error = root.mul_add(root, -number);
+            match (up, error) {
+                (false, error) if error > 0.0 => root.next_down(),
+                (true, error) if error < 0.0 => root.next_up(),
+                _ => root
+            }
+        };
+        Interval::finite(root(self.lo, false), root(self.hi, true))
+    }
+
+    pub fn abs(&self) -> Interval {
+        if self.contains(0.0) {
+            Interval { lo: 0.0, hi: self.lo.abs().max(self.hi.abs()) }
+        }
+        else {
+            Interval { lo: self.lo.abs().min(self.hi.abs()), hi: self.lo.abs().max(self.hi.abs()) }
+        }
+    }
+
+    pub fn max(&self, other: &Interval) -> Interval {
+        Interval { lo: self.lo.max(other.lo), hi: self.hi.max(other.hi) }
+    }
+
+    pub fn min(&self, other: &Interval) -> Interval {
+        Interval { lo: self.lo.min(other.lo), hi: self.hi.min(other.hi) }
+    }
+
+    pub fn try_add(self, other: Interval) -> Result<Interval, ErrorType> {
+        Interval::finite(add_down(self.lo, other.lo), add_up(self.hi, other.hi))
+    }
+
+    pub fn try_sub(self, other: Interval) -> Result<Interval, ErrorType> {
+        Interval::finite(add_down(self.lo, -other.hi), add_up(self.hi, -other.lo))
+    }
+
+    pub fn try_mul(self, other: Interval) -> Result<Interval, ErrorType> {
+        Interval::corners(self, other, mul_down, mul_up)
+    }
+
+    //dividing by an interval which holds zero is an InvalidOperation
+    pub fn try_div(self, other: Interval) -> Result<Interval, ErrorType> {
+        if other.contains(0.0) {
+            return Error::throw(ErrorType::InvalidOperation);
+        }
+        Interval::corners(self, other, div_down, div_up)
+    }
+
+    //an integer exponent keeps the sign rules of powers, any other one needs a base which is not negative
+    pub fn pow(&self, exponent: &Interval) -> Result<Interval, ErrorType> {
+        match exponent.to_point() {
+            Some(n) if n.fract() == 0.0 && n.abs() <= i32::MAX as f64 => self.powi(n as i32),
+            _ if self.lo < 0.0 => Error::throw(ErrorType::InvalidOperation),
+            _ => {
+                let corners = [
+                    self.lo.powf(exponent.lo), self.lo.powf(exponent.hi),
+                    self.hi.powf(exponent.lo), self.hi.powf(exponent.hi),
+                ];
+                Interval::hull(&corners)
+            }
+        }
+    }
+
+    //remainder by a positive number, a whole interval within one period keeps its width
+    pub fn rem(&self, divisor: &Interval) -> Result<Interval, ErrorType> {
+        match divisor.to_point() {
+            Some(divisor) if divisor > 0.0 && self.lo >= 0.0 => {
+                //a floating point remainder is exact
+                if (self.lo / divisor).floor() == (self.hi / divisor).floor() {
+                    Interval::new(self.lo % divisor, self.hi % divisor)
+                }
+                else {
+                    Interval::new(0.0, divisor)
+                }
+            }
+            _ => Error::throw(ErrorType::InvalidOperation)
+        }
+    }
+
+    fn powi(&self, exponent: i32) -> Result<Interval, ErrorType> {
+        if exponent < 0 {
+            return Interval::point(1.0).try_div(self.powi(-exponent)?);
+        }
+        let exponent = exponent as u32;
+        match exponent % 2 {
+            0 if self.contains(0.0) => Interval::finite(0.0, pow_up(self.lo.abs(), exponent).max(pow_up(self.hi.abs(), exponent))),
+            0 if self.hi < 0.0 => Interval::finite(pow_down(-self.hi, exponent), pow_up(-self.lo, exponent)),
+            0 => Interval::finite(pow_down(self.lo, exponent), pow_up(self.hi, exponent)),
+            _ => Interval::finite(odd_pow(self.lo, exponent, false), odd_pow(self.hi, exponent, true)),
+        }
+    }
+
+    //bounds of a product or quotient, the least of the corners rounded down and the greatest rounded up
+    fn corners(self, other: Interval, down: fn(f64, f64) -> f64, up: fn(f64, f64) -> f64) -> Result<Interval, ErrorType> {
+        let pairs = [(self.lo, other.lo), (self.lo, other.hi), (self.hi, other.lo), (self.hi, other.hi)];
+        let lo = pairs.iter().map(|&(a, b)| down(a, b)).fold(f64::INFINITY, f64::min);
+        let hi = pairs.iter().map(|&(a, b)| up(a, b)).fold(f64::NEG_INFINITY, f64::max);
+        Interval::finite(lo, hi)
+    }
+
+    //smallest interval holding every number, an InvalidOperation if one is not finite
+    fn hull(numbers: &[f64]) -> Result<Interval, ErrorType> {
+        let lo = numbers.iter().copied().fold(f64::INFINITY, f64::min);
+        let hi = numbers.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        Interval::outward(lo, hi)
+    }
+
+    //bounds computed by functions whose rounding is unknown, widened by one unit in the last place
+    fn outward(lo: f64, hi: f64) -> Result<Interval, ErrorType> {
+        Interval::finite(lo.next_down(), hi.next_up())
+    }
+
+    //bounds already rounded outwards
+    fn finite(lo: f64, hi: f64) -> Result<Interval, ErrorType> {
+        if !lo.is_finite() || !hi.is_finite() {
+            return Error::throw(ErrorType::InvalidOperation);
+        }
+        Interval::new(lo, hi)
+    }
+}
+
+//the error of a floating point sum, product or quotient is computed exactly
+//a bound moves by one unit in the last place only when the rounded result is on the wrong side of the exact one
+fn add_down(a: f64, b: f64) -> f64 {
+    let sum = a + b;
+    if sum_error(a, b, sum) < 0.0 { sum.next_down() } else { sum }
+}
+
+fn add_up(a: f64, b: f64) -> f64 {
+    let sum = a + b;
+    if sum_error(a, b, sum) > 0.0 { sum.next_up() } else { sum }
+}
+
+//exact sum minus the rounded one
+fn sum_error(a: f64, b: f64, sum: f64) -> f64 {
+    let b_part = sum - a;
+    (a - (sum - b_part)) + (b - b_part)
+}
+
+fn mul_down(a: f64, b: f64) -> f64 {
+    let product = a * b;
+    if a.mul_add(b, -product) < 0.0 { product.next_down() } else { product }
+}
+
+fn mul_up(a: f64, b: f64) -> f64 {
+    let product = a * b;
+    if a.mul_add(b, -product) > 0.0 { product.next_up() } else { product }
+}
+
+//the exact quotient is above the rounded one when the remainder has the sign of the divisor
+fn div_down(a: f64, b: f64) -> f64 {
+    let quotient = a / b;
+    let remainder = (-quotient).mul_add(b, a);
+    if remainder != 0.0 && (remainder > 0.0) != (b > 0.0) { quotient.next_down() } else { quotient }
+}
+
+fn div_up(a: f64, b: f64) -> f64 {
+    let quotient = a / b;
+    let remainder = (-quotient).mul_add(b, a);
+    if remainder != 0.0 && (remainder > 0.0) == (b > 0.0) { quotient.next_up() } else { quotient }
+}
+
+//powers of a number which is not negative by squaring, every step rounded the same way
+fn pow_down(base: f64, exponent: u32) -> f64 {
+    power(base, exponent, mul_down)
+}
+
+fn pow_up(base: f64, exponent: u32) -> f64 {
+    power(base, exponent, mul_up)
+}
+
+fn power(base: f64, exponent: u32, multiply: fn(f64, f64) -> f64) -> f64 {
+    let (mut result, mut base, mut exponent) = (1.0, base, exponent);
+    while exponent > 0 {
+        if exponent % 2 == 1 {
+            result = multiply(result, base);
+        }
+        exponent /= 2;
+        if exponent > 0 {
+            base = multiply(base, base);
+        }
+    }
+    result
+}
+
+//an odd power of a negative number is minus the power of its opposite, rounded the other way
+fn odd_pow(base: f64, exponent: u32, up: bool) -> f64 {
+    match (base < 0.0, up) {
+        (true, true) => -pow_down(-base, exponent),
+        (true, false) => -pow_up(-base, exponent),
+        (false, true) => pow_up(base, exponent),
+        (false, false) => pow_down(base, exponent),
+    }
+}
+
+impl Neg for Interval {
+    type Output = Interval;
+
+    fn neg(self) -> Interval {
+        Interval { lo: -self.hi, hi: -self.lo }
+    }
+}
+
+impl Display for Interval {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        //bounds widened away from zero are tiny, they are written with an exponent
+        let bound = |number: f64| if number != 0.0 && number.abs() < 1e-9 { format!("{:e}", number) } else { number.to_string() };
+        write!(f, "[{}, {}]", bound(self.lo), bound(self.hi))
+    }
+}
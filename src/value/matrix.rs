@@ -0,0 +1,232 @@
+use std::fmt::{Display, Formatter};
+use crate::errors::{Error, ErrorType};
+use crate::symbolic::Expr;
+use crate::value::Value;
+
+//pivots smaller than this relative to the largest element are taken as zero when eliminating
+const EPSILON: f64 = 1e-10;
+
+//rows of numbers of the same length, stored one row after the other
+#[derive(Debug, Clone, PartialEq)]
+pub struct Matrix {
+    rows: usize,
+    columns: usize,
+    elements: Vec<f64>,
+}
+
+impl Matrix {
+    pub fn new(rows: usize, columns: usize, elements: Vec<f64>) -> Matrix {
+        debug_assert_eq!(elements.len(), rows * columns);
+        Matrix { rows, columns, elements }
+    }
+
+    pub fn identity(size: usize) -> Matrix {
+        let elements = (0..size * size).map(|i| if i / size == i % size { 1.0 } else { 0.0 }).collect();
+        Matrix::new(size, size, elements)
+    }
+
+    //matrix of a list of rows, None unless they are non empty lists of numbers
+    //rows of different lengths are a ShapeMismatch
+    pub fn from_rows(rows: &[Value]) -> Result<Option<Matrix>, ErrorType> {
+        let mut elements: Vec<f64> = Vec::new();
+        let mut columns: Option<usize> = None;
+        for row in rows {
+            let Value::List(row) = row else { return Ok(None) };
+            match columns {
+                Some(columns) if columns != row.len() => return Error::throw(ErrorType::ShapeMismatch),
+                _ => columns = Some(row.len()),
+            }
+            for value in row {
+                let Some(number) = value.to_number() else { return Ok(None) };
+                elements.push(number);
+            }
+        }
+        match columns {
+            Some(columns) if columns > 0 => Ok(Some(Matrix::new(rows.len(), columns, elements))),
+            _ => Ok(None)
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    //exact element, element rounds it for display
+    pub fn get(&self, row: usize, column: usize) -> f64 {
+        self.elements[row * self.columns + column]
+    }
+
+    pub fn element(&self, row: usize, column: usize) -> Value {
+        Value::from_number(Expr::round_significant(self.get(row, column)))
+    }
+
+    pub fn row(&self, row: usize) -> Value {
+        Value::List((0..self.columns).map(|column| self.element(row, column)).collect())
+    }
+
+    //every element in order, e.g. the entries of a single row or column
+    pub fn to_values(&self) -> Vec<Value> {
+        self.elements.iter().map(|element| Value::from_number(Expr::round_significant(*element))).collect()
+    }
+
+    pub fn to_rows(&self) -> Vec<Value> {
+        (0..self.rows).map(|row| self.row(row)).collect()
+    }
+
+    pub fn map(&self, f: impl Fn(f64) -> Result<f64, ErrorType>) -> Result<Matrix, ErrorType> {
+        let elements = self.elements.iter().map(|element| f(*element)).collect::<Result<_, _>>()?;
+        Ok(Matrix::new(self.rows, self.columns, elements))
+    }
+
+    //element by element, both matrices must have the same shape
+    pub fn zip_with(&self, other: &Matrix, f: impl Fn(f64, f64) -> Result<f64, ErrorType>) -> Result<Matrix, ErrorType> {
+        if self.rows != other.rows || self.columns != other.columns {
+            return Error::throw(ErrorType::ShapeMismatch);
+        }
+        let elements = self.elements.iter().zip(&other.elements).map(|(a, b)| f(*a, *b)).collect::<Result<_, _>>()?;
+        Ok(Matrix::new(self.rows, self.columns, elements))
+    }
+
+    pub fn transpose(&self) -> Matrix {
+        let elements = (0..self.columns)
+            .flat_map(|column| (0..self.rows).map(move |row| (row, column)))
+            .map(|(row, column)| self.get(row, column))
+            .collect();
+        Matrix::new(self.columns, self.rows, elements)
+    }
+
+    pub fn product(&self, other: &Matrix) -> Result<Matrix, ErrorType> {
+        if self.columns != other.rows {
+            return Error::throw(ErrorType::ShapeMismatch);
+        }
+        let elements = (0..self.rows)
+            .flat_map(|row| (0..other.columns).map(move |column| (row, column)))
+            .map(|(row, column)| (0..self.columns).map(|k| self.get(row, k) * other.get(k, column)).sum())
+            .collect();
+        Ok(Matrix::new(self.rows, other.columns, elements))
+    }
+
+    //repeated squaring, a negative exponent is a power of the inverse
+    pub fn power(&self, exponent: i64) -> Result<Matrix, ErrorType> {
+        self.require_square()?;
+        let mut base = if exponent < 0 { self.inverse()? } else { self.clone() };
+        let mut exponent = exponent.unsigned_abs();
+        let mut result = Matrix::identity(self.rows);
+        while exponent > 0 {
+            if exponent % 2 == 1 {
+                result = result.product(&base)?;
+            }
+            base = base.product(&base)?;
+            exponent /= 2;
+        }
+        Ok(result)
+    }
+
+    pub fn determinant(&self) -> Result<f64, ErrorType> {
+        self.require_square()?;
+        let (_, pivots, determinant) = self.reduce(self.columns);
+        Ok(if pivots < self.rows { 0.0 } else { Expr::round_off(determinant) })
+    }
+
+    //an InvalidOperation for a singular matrix
+    pub fn inverse(&self) -> Result<Matrix, ErrorType> {
+        self.require_square()?;
+        let reduced = self.augment(&Matrix::identity(self.rows)).reduce_square()?;
+        let elements = (0..self.rows)
+            .flat_map(|row| (self.columns..2 * self.columns).map(move |column| (row, column)))
+            .map(|(row, column)| reduced.get(row, column))
+            .collect();
+        Ok(Matrix::new(self.rows, self.columns, elements))
+    }
+
+    pub fn rank(&self) -> usize {
+        self.reduce(self.columns).1
+    }
+
+    //x such that self x = b, self must be square and invertible
+    pub fn solve(&self, b: &[f64]) -> Result<Vec<f64>, ErrorType> {
+        self.require_square()?;
+        if b.len() != self.rows {
+            return Error::throw(ErrorType::ShapeMismatch);
+        }
+        let reduced = self.augment(&Matrix::new(b.len(), 1, b.to_vec())).reduce_square()?;
+        Ok((0..self.rows).map(|row| Expr::round_significant(reduced.get(row, self.columns))).collect())
+    }
+
+    fn require_square(&self) -> Result<(), ErrorType> {
+        if self.rows != self.columns {
+            return Error::throw(ErrorType::ShapeMismatch);
+        }
+        Ok(())
+    }
+
+    //columns of other appended to the right of self
+    fn augment(&self, other: &Matrix) -> Matrix {
+        let columns = self.columns + other.columns;
+        let elements = (0..self.rows)
+            .flat_map(|row| (0..columns).map(move |column| (row, column)))
+            .map(|(row, column)| if column < self.columns { self.get(row, column) } else { other.get(row, column - self.columns) })
+            .collect();
+        Matrix::new(self.rows, columns, elements)
+    }
+
+    //reduce an augmented [A | B] with A square, an InvalidOperation if A is singular
+    fn reduce_square(&self) -> Result<Matrix, ErrorType> {
+        let (reduced, pivots, _) = self.reduce(self.rows);
+        if pivots < self.rows {
+            return Error::throw(ErrorType::InvalidOperation);
+        }
+        Ok(reduced)
+    }
+
+    //Gauss-Jordan elimination with partial pivoting on the first pivot_columns columns
+    //returns the reduced matrix, the number of pivots and the product of the pivots with the sign of the row swaps
+    fn reduce(&self, pivot_columns: usize) -> (Matrix, usize, f64) {
+        let mut matrix = self.clone();
+        let scale = (0..self.rows)
+            .flat_map(|row| (0..pivot_columns).map(move |column| (row, column)))
+            .fold(0.0, |scale: f64, (row, column)| scale.max(self.get(row, column).abs()));
+        let mut determinant = 1.0;
+        let mut row = 0;
+        for column in 0..pivot_columns {
+            if row == matrix.rows {
+                break;
+            }
+            let best = (row..matrix.rows)
+                .max_by(|a, b| matrix.get(*a, column).abs().total_cmp(&matrix.get(*b, column).abs()))
+                .unwrap();
+            if matrix.get(best, column).abs() <= EPSILON * scale {
+                continue;
+            }
+            if best != row {
+                for k in 0..matrix.columns {
+                    matrix.elements.swap(best * matrix.columns + k, row * matrix.columns + k);
+                }
+                determinant = -determinant;
+            }
+            let pivot = matrix.get(row, column);
+            determinant *= pivot;
+            for k in 0..matrix.columns {
+                matrix.elements[row * matrix.columns + k] /= pivot;
+            }
+            for other in (0..matrix.rows).filter(|other| *other != row) {
+                let factor = matrix.get(other, column);
+                for k in 0..matrix.columns {
+                    matrix.elements[other * matrix.columns + k] -= factor * matrix.get(row, k);
+                }
+            }
+            row += 1;
+        }
+        (matrix, row, determinant)
+    }
+}
+
+impl Display for Matrix {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", Value::List(self.to_rows()))
+    }
+}
@@ -0,0 +1,193 @@
+pub mod complex;
+pub mod date;
+pub mod decimal;
+pub mod interval;
+pub mod matrix;
+pub mod natural;
+pub mod quantity;
+pub mod rational;
+pub mod text;
+pub mod unit;
+
+use std::fmt::{Display, Formatter};
+use crate::errors::{Error, ErrorType};
+use crate::symbolic::Expr;
+use crate::value::complex::Complex;
+use crate::value::date::Date;
+use crate::value::decimal::Decimal;
+use crate::value::interval::Interval;
+use crate::value::matrix::Matrix;
+use crate::value::quantity::Quantity;
+use crate::value::rational::Rational;
+
+//what the executor works with, a number, an unevaluated expression or several values
+//numbers are promoted from integer to fraction to floating point number when a result needs it
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Integer(i64),
+    Rational(Rational),
+    Bool(bool),
+    Expression(Expr),
+    List(Vec<Value>),
+    Matrix(Matrix),
+    Complex(Complex),
+    Quantity(Quantity),
+    Decimal(Decimal),
+    Interval(Interval),
+    Date(Date),
+    Text(String),
+}
+
+impl Value {
+    //a single value as is, several ones as a list
+    pub fn from_values(mut values: Vec<Value>) -> Value {
+        match values.len() {
+            1 => values.pop().unwrap(),
+            _ => Value::List(values)
+        }
+    }
+
+    //value of a list literal, rows of numbers of the same length make a matrix
+    pub fn from_elements(values: Vec<Value>) -> Result<Value, ErrorType> {
+        Ok(match Matrix::from_rows(&values)? {
+            Some(matrix) => Value::Matrix(matrix),
+            None => Value::List(values)
+        })
+    }
+
+    //a computed number, printed as it is
+    pub fn from_number(number: f64) -> Value {
+        Value::from(Expr::Number(number))
+    }
+
+    //an exact fraction, an integer when it is a whole number
+    pub fn from_rational(number: Rational) -> Value {
+        if number.is_integer() { Value::Integer(number.numerator()) } else { Value::Rational(number) }
+    }
+
+    //a computed complex number, a real one when its imaginary part is zero
+    pub fn from_complex(number: Complex) -> Value {
+        match number.clean() {
+            Complex { re, im: 0.0 } => Value::from_number(re),
+            number => Value::Complex(number)
+        }
+    }
+
+    //a computed quantity, a plain number when its unit has no dimension
+    pub fn from_quantity(quantity: Quantity) -> Value {
+        if quantity.unit().is_dimensionless() { Value::from_number(quantity.magnitude()) } else { Value::Quantity(quantity) }
+    }
+
+    pub fn to_quantity(&self) -> Option<Quantity> {
+        match self {
+            Value::Quantity(quantity) => Some(quantity.clone()),
+            value => value.to_number().map(Quantity::scalar)
+        }
+    }
+
+    //exact fraction of an integer, a fraction or a decimal which fits one
+    pub fn to_rational(&self) -> Option<Rational> {
+        match self {
+            Value::Integer(number) => Some(Rational::from_integer(*number)),
+            Value::Rational(number) => Some(*number),
+            Value::Decimal(number) => number.to_rational(),
+            _ => None
+        }
+    }
+
+    //exact decimal of an integer or a decimal, floating point numbers have none
+    pub fn to_decimal(&self) -> Option<Decimal> {
+        match self {
+            Value::Integer(number) => Some(Decimal::from_integer(*number)),
+            Value::Decimal(number) => Some(number.clone()),
+            _ => None
+        }
+    }
+
+    //interval of a single number for plain numbers
+    pub fn to_interval(&self) -> Option<Interval> {
+        match self {
+            Value::Interval(interval) => Some(*interval),
+            value => value.to_number().map(Interval::point)
+        }
+    }
+
+    pub fn to_complex(&self) -> Option<Complex> {
+        match self {
+            Value::Complex(number) => Some(*number),
+            value => value.to_number().map(|number| Complex::new(number, 0.0))
+        }
+    }
+
+    pub fn to_number(&self) -> Option<f64> {
+        match self {
+            Value::Integer(number) => Some(*number as f64),
+            Value::Rational(number) => Some(number.to_f64()),
+            Value::Expression(Expr::Number(number)) => Some(*number),
+            Value::Decimal(number) => Some(number.to_f64()),
+            _ => None
+        }
+    }
+}
+
+impl From<Expr> for Value {
+    //constant expressions with an integer value become plain integers
+    fn from(expr: Expr) -> Self {
+        match expr {
+            Expr::Number(number) if number.fract() == 0.0 && number.abs() < i64::MAX as f64 => {
+                Value::Integer(number as i64)
+            }
+            expr => Value::Expression(expr)
+        }
+    }
+}
+
+impl TryFrom<Value> for Expr {
+    type Error = ErrorType;
+
+    fn try_from(value: Value) -> Result<Self, ErrorType> {
+        match value {
+            Value::Integer(number) => Ok(Expr::Number(number as f64)),
+            Value::Rational(number) => Ok(Expr::Number(number.to_f64())),
+            Value::Expression(expr) => Ok(expr),
+            Value::Decimal(number) => Ok(Expr::Number(number.to_f64())),
+            Value::List(_) | Value::Matrix(_) | Value::Complex(_) | Value::Quantity(_) | Value::Interval(_) |
+            Value::Date(_) => {
+                Error::throw(ErrorType::InvalidOperation)
+            }
+            Value::Text(_) | Value::Bool(_) => Error::throw(ErrorType::TypeError),
+        }
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Integer(number) => write!(f, "{}", number),
+            Value::Rational(number) => write!(f, "{}", number),
+            Value::Bool(value) => write!(f, "{}", value),
+            Value::Expression(expr) => write!(f, "{}", expr),
+            Value::List(values) => {
+                write!(f, "[")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    //texts of a list are quoted so their commas do not look like separators
+                    match value {
+                        Value::Text(text) => write!(f, "{}", Value::quoted(text))?,
+                        value => write!(f, "{}", value)?,
+                    }
+                }
+                write!(f, "]")
+            }
+            Value::Matrix(matrix) => write!(f, "{}", matrix),
+            Value::Complex(number) => write!(f, "{}", number),
+            Value::Quantity(quantity) => write!(f, "{}", quantity),
+            Value::Decimal(number) => write!(f, "{}", number),
+            Value::Interval(interval) => write!(f, "{}", interval),
+            Value::Date(date) => write!(f, "{}", date),
+            Value::Text(text) => write!(f, "{}", text),
+        }
+    }
+}
@@ -0,0 +1,270 @@
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+
+//digits of a limb when printing, 10^9 is the largest power of ten in a u32
+const DECIMAL_LIMB: u32 = 1_000_000_000;
+const DECIMAL_LIMB_DIGITS: usize = 9;
+
+//natural number of any size, limbs in base 2^32 from the least significant one, without leading zeros
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Natural {
+    limbs: Vec<u32>,
+}
+
+impl Natural {
+    pub fn zero() -> Natural {
+        Natural { limbs: Vec::new() }
+    }
+
+    pub fn from_u64(number: u64) -> Natural {
+        Natural { limbs: vec![number as u32, (number >> 32) as u32] }.normalized()
+    }
+
+    //digits of a decimal number, None if there is anything else
+    pub fn parse(digits: &str) -> Option<Natural> {
+        if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        let mut number = Natural::zero();
+        for chunk in digits.as_bytes().chunks(DECIMAL_LIMB_DIGITS) {
+            let chunk = std::str::from_utf8(chunk).ok()?;
+            number = number.mul_small(10u32.pow(chunk.len() as u32)).add_small(chunk.parse().ok()?);
+        }
+        Some(number)
+    }
+
+    pub fn power_of_ten(exponent: u32) -> Natural {
+        let mut power = Natural::from_u64(1);
+        let mut remaining = exponent;
+        while remaining > 0 {
+            let step = remaining.min(DECIMAL_LIMB_DIGITS as u32);
+            power = power.mul_small(10u32.pow(step));
+            remaining -= step;
+        }
+        power
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    pub fn is_even(&self) -> bool {
+        self.limbs.first().is_none_or(|limb| limb % 2 == 0)
+    }
+
+    //None past the u64 range
+    pub fn to_u64(&self) -> Option<u64> {
+        match self.limbs.as_slice() {
+            [] => Some(0),
+            [low] => Some(*low as u64),
+            [low, high] => Some((*high as u64) << 32 | *low as u64),
+            _ => None
+        }
+    }
+
+    pub fn add(&self, other: &Natural) -> Natural {
+        let mut limbs = Vec::with_capacity(self.limbs.len().max(other.limbs.len()) + 1);
+        let mut carry = 0u64;
+        for i in 0..self.limbs.len().max(other.limbs.len()) {
+            let sum = self.limb(i) as u64 + other.limb(i) as u64 + carry;
+            limbs.push(sum as u32);
+            carry = sum >> 32;
+        }
+        limbs.push(carry as u32);
+        Natural { limbs }.normalized()
+    }
+
+    //self - other, other must not be larger
+    pub fn sub(&self, other: &Natural) -> Natural {
+        let mut limbs = Vec::with_capacity(self.limbs.len());
+        let mut borrow = 0i64;
+        for i in 0..self.limbs.len() {
+            let difference = self.limb(i) as i64 - other.limb(i) as i64 - borrow;
+            limbs.push(difference as u32);
+            borrow = (difference < 0) as i64;
+        }
+        Natural { limbs }.normalized()
+    }
+
+    pub fn mul(&self, other: &Natural) -> Natural {
+        if self.is_zero() || other.is_zero() {
+            return Natural::zero();
+        }
+        let mut limbs = vec![0u32; self.limbs.len() + other.limbs.len()];
+        for (i, a) in self.limbs.iter().enumerate() {
+            let mut carry = 0u64;
+            for (j, b) in other.limbs.iter().enumerate() {
+                let product = *a as u64 * *b as u64 + limbs[i + j] as u64 + carry;
+                limbs[i + j] = product as u32;
+                carry = product >> 32;
+            }
+            limbs[i + other.limbs.len()] = carry as u32;
+        }
+        Natural { limbs }.normalized()
+    }
+
+    //quotient and remainder, the divisor must not be zero
+    pub fn div_rem(&self, divisor: &Natural) -> (Natural, Natural) {
+        if self < divisor {
+            return (Natural::zero(), self.clone());
+        }
+        if let [divisor] = divisor.limbs.as_slice() {
+            let (quotient, remainder) = self.div_rem_small(*divisor);
+            return (quotient, Natural::from_u64(remainder as u64));
+        }
+        self.div_rem_long(divisor)
+    }
+
+    //largest natural whose square is at most self, by Newton's method
+    pub fn sqrt(&self) -> Natural {
+        if self.is_zero() {
+            return Natural::zero();
+        }
+        let bits = self.limbs.len() * 32;
+        let mut root = Natural::from_u64(1).shl(bits / 2 + 1);
+        loop {
+            let next = root.add(&self.div_rem(&root).0).shr(1);
+            if next >= root {
+                return root;
+            }
+            root = next;
+        }
+    }
+
+    fn limb(&self, i: usize) -> u32 {
+        self.limbs.get(i).copied().unwrap_or(0)
+    }
+
+    fn normalized(mut self) -> Natural {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+        self
+    }
+
+    fn mul_small(&self, factor: u32) -> Natural {
+        self.mul(&Natural::from_u64(factor as u64))
+    }
+
+    fn add_small(&self, term: u32) -> Natural {
+        self.add(&Natural::from_u64(term as u64))
+    }
+
+    fn div_rem_small(&self, divisor: u32) -> (Natural, u32) {
+        let mut limbs = vec![0u32; self.limbs.len()];
+        let mut remainder = 0u64;
+        for i in (0..self.limbs.len()).rev() {
+            let current = remainder << 32 | self.limbs[i] as u64;
+            limbs[i] = (current / divisor as u64) as u32;
+            remainder = current % divisor as u64;
+        }
+        (Natural { limbs }.normalized(), remainder as u32)
+    }
+
+    fn shl(&self, bits: usize) -> Natural {
+        let (whole, part) = (bits / 32, bits % 32);
+        let mut limbs = vec![0u32; whole];
+        let mut carry = 0u32;
+        for limb in &self.limbs {
+            limbs.push(if part == 0 { *limb } else { limb << part | carry });
+            carry = if part == 0 { 0 } else { limb >> (32 - part) };
+        }
+        limbs.push(carry);
+        Natural { limbs }.normalized()
+    }
+
+    //shift right by less than a limb
+    fn shr(&self, bits: usize) -> Natural {
+        if bits == 0 {
+            return self.clone();
+        }
+        let limbs = (0..self.limbs.len())
+            .map(|i| self.limbs[i] >> bits | self.limb(i + 1) << (32 - bits))
+            .collect();
+        Natural { limbs }.normalized()
+    }
+
+    //Knuth's algorithm D, the divisor has at least two limbs and is at most self
+    fn div_rem_long(&self, divisor: &Natural) -> (Natural, Natural) {
+        let shift = divisor.limbs.last().unwrap().leading_zeros() as usize;
+        let v = divisor.shl(shift).limbs;
+        let mut u = self.shl(shift).limbs;
+        u.resize(self.limbs.len() + 1, 0);
+        let n = v.len();
+        let m = u.len() - n;
+        let base = 1u64 << 32;
+        let mut quotient = vec![0u32; m];
+        for j in (0..m).rev() {
+            let numerator = (u[j + n] as u64) << 32 | u[j + n - 1] as u64;
+            let mut estimate = numerator / v[n - 1] as u64;
+            let mut rest = numerator % v[n - 1] as u64;
+            while estimate >= base || estimate * v[n - 2] as u64 > (rest << 32 | u[j + n - 2] as u64) {
+                estimate -= 1;
+                rest += v[n - 1] as u64;
+                if rest >= base {
+                    break;
+                }
+            }
+            let mut borrow = 0i64;
+            let mut carry = 0u64;
+            for i in 0..n {
+                let product = estimate * v[i] as u64 + carry;
+                carry = product >> 32;
+                let difference = u[i + j] as i64 - borrow - (product & 0xffff_ffff) as i64;
+                u[i + j] = difference as u32;
+                borrow = (difference < 0) as i64;
+            }
+            let difference = u[j + n] as i64 - borrow - carry as i64;
+            u[j + n] = difference as u32;
+            quotient[j] = estimate as u32;
+            //the estimate was one too large, add the divisor back
+            if difference < 0 {
+                quotient[j] -= 1;
+                let mut carry = 0u64;
+                for i in 0..n {
+                    let sum = u[i + j] as u64 + v[i] as u64 + carry;
+                    u[i + j] = sum as u32;
+                    carry = sum >> 32;
+                }
+                u[j + n] = u[j + n].wrapping_add(carry as u32);
+            }
+        }
+        u.truncate(n);
+        (Natural { limbs: quotient }.normalized(), Natural { limbs: u }.normalized().shr(shift))
+    }
+}
+
+impl Ord for Natural {
+    fn cmp(&self, other: &Natural) -> Ordering {
+        self.limbs.len().cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for Natural {
+    fn partial_cmp(&self, other: &Natural) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Display for Natural {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut chunks: Vec<u32> = Vec::new();
+        let mut rest = self.clone();
+        while !rest.is_zero() {
+            let (quotient, chunk) = rest.div_rem_small(DECIMAL_LIMB);
+            chunks.push(chunk);
+            rest = quotient;
+        }
+        match chunks.pop() {
+            None => write!(f, "0"),
+            Some(first) => {
+                write!(f, "{}", first)?;
+                for chunk in chunks.iter().rev() {
+                    write!(f, "{:0width$}", chunk, width = DECIMAL_LIMB_DIGITS)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use std::fmt::{Display, Formatter};
+use std::ops::{Mul, Neg};
+use crate::errors::{Error, ErrorType};
+use crate::symbolic::Expr;
+use crate::value::unit::Unit;
+use crate::value::Value;
+
+//a number with a unit, kept in SI base units and shown in its own unit
+#[derive(Debug, Clone, PartialEq)]
+pub struct Quantity {
+    magnitude: f64,
+    unit: Unit,
+}
+
+impl Quantity {
+    //value written in unit, 3 km is Quantity::new(3.0, km)
+    pub fn new(value: f64, unit: Unit) -> Quantity {
+        Quantity { magnitude: value * unit.factor(), unit }
+    }
+
+    pub fn scalar(value: f64) -> Quantity {
+        Quantity::new(value, Unit::none())
+    }
+
+    pub fn unit(&self) -> &Unit {
+        &self.unit
+    }
+
+    //value in SI base units
+    pub fn magnitude(&self) -> f64 {
+        self.magnitude
+    }
+
+    //value in its own unit
+    pub fn value(&self) -> f64 {
+        self.magnitude / self.unit.factor()
+    }
+
+    //same quantity in another unit of the same dimension
+    pub fn convert(&self, unit: &Unit) -> Result<Quantity, ErrorType> {
+        self.require_dimension(unit)?;
+        Ok(Quantity { magnitude: self.magnitude, unit: unit.clone() })
+    }
+
+    pub fn powi(&self, exponent: i32) -> Quantity {
+        Quantity { magnitude: self.magnitude.powi(exponent), unit: self.unit.powi(exponent) }
+    }
+
+    //an InvalidOperation unless every exponent of the unit is even
+    pub fn sqrt(&self) -> Result<Quantity, ErrorType> {
+        match self.unit.sqrt() {
+            Some(unit) if self.magnitude >= 0.0 => Ok(Quantity { magnitude: self.magnitude.sqrt(), unit }),
+            _ => Error::throw(ErrorType::InvalidOperation)
+        }
+    }
+
+    pub fn abs(&self) -> Quantity {
+        Quantity { magnitude: self.magnitude.abs(), unit: self.unit.clone() }
+    }
+
+    //a sum is in the unit of its left operand, 3 km + 200 m is 3.2 km
+    pub fn try_add(self, other: Quantity) -> Result<Quantity, ErrorType> {
+        self.require_dimension(&other.unit)?;
+        Ok(Quantity { magnitude: self.magnitude + other.magnitude, unit: self.unit })
+    }
+
+    pub fn try_sub(self, other: Quantity) -> Result<Quantity, ErrorType> {
+        self.require_dimension(&other.unit)?;
+        Ok(Quantity { magnitude: self.magnitude - other.magnitude, unit: self.unit })
+    }
+
+    pub fn try_rem(self, other: Quantity) -> Result<Quantity, ErrorType> {
+        self.require_dimension(&other.unit)?;
+        if other.magnitude == 0.0 {
+            return Error::throw(ErrorType::InvalidOperation);
+        }
+        Ok(Quantity { magnitude: self.magnitude % other.magnitude, unit: self.unit })
+    }
+
+    pub fn try_div(self, other: Quantity) -> Result<Quantity, ErrorType> {
+        if other.magnitude == 0.0 {
+            return Error::throw(ErrorType::InvalidOperation);
+        }
+        Ok(Quantity { magnitude: self.magnitude / other.magnitude, unit: self.unit.product(&other.unit.powi(-1)) })
+    }
+
+    fn require_dimension(&self, unit: &Unit) -> Result<(), ErrorType> {
+        if self.unit.dimension() != unit.dimension() {
+            return Error::throw(ErrorType::DimensionMismatch);
+        }
+        Ok(())
+    }
+}
+
+impl Mul for Quantity {
+    type Output = Quantity;
+
+    fn mul(self, other: Quantity) -> Quantity {
+        Quantity { magnitude: self.magnitude * other.magnitude, unit: self.unit.product(&other.unit) }
+    }
+}
+
+impl Neg for Quantity {
+    type Output = Quantity;
+
+    fn neg(self) -> Quantity {
+        Quantity { magnitude: -self.magnitude, unit: self.unit }
+    }
+}
+
+impl Display for Quantity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", Value::from_number(Expr::round_significant(self.value())), self.unit)
+    }
+}
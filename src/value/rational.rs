@@ -0,0 +1,122 @@
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+
+//exact fraction in lowest terms, the denominator is positive
+//operations return None when a result does not fit an i64, it is then a floating point number
+//they also return None for a zero denominator, callers check their divisor first
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rational {
+    numerator: i64,
+    denominator: i64,
+}
+
+impl Rational {
+    pub fn new(numerator: i64, denominator: i64) -> Option<Rational> {
+        Rational::reduced(numerator as i128, denominator as i128)
+    }
+
+    pub fn from_integer(number: i64) -> Rational {
+        Rational { numerator: number, denominator: 1 }
+    }
+
+    pub fn numerator(&self) -> i64 {
+        self.numerator
+    }
+
+    pub fn denominator(&self) -> i64 {
+        self.denominator
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denominator == 1
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        self.numerator as f64 / self.denominator as f64
+    }
+
+    pub fn checked_add(&self, other: &Rational) -> Option<Rational> {
+        let (a, b, c, d) = self.widened(other);
+        Rational::reduced(a * d + c * b, b * d)
+    }
+
+    pub fn checked_sub(&self, other: &Rational) -> Option<Rational> {
+        let (a, b, c, d) = self.widened(other);
+        Rational::reduced(a * d - c * b, b * d)
+    }
+
+    pub fn checked_mul(&self, other: &Rational) -> Option<Rational> {
+        let (a, b, c, d) = self.widened(other);
+        Rational::reduced(a * c, b * d)
+    }
+
+    pub fn checked_div(&self, other: &Rational) -> Option<Rational> {
+        let (a, b, c, d) = self.widened(other);
+        Rational::reduced(a * d, b * c)
+    }
+
+    //remainder of the quotient truncated towards zero, it has the sign of self
+    pub fn checked_rem(&self, other: &Rational) -> Option<Rational> {
+        let (a, b, c, d) = self.widened(other);
+        if c == 0 {
+            return None;
+        }
+        Rational::reduced((a * d) % (c * b), b * d)
+    }
+
+    //a negative exponent is a power of the inverse
+    pub fn checked_powi(&self, exponent: i64) -> Option<Rational> {
+        let base = if exponent < 0 { Rational::from_integer(1).checked_div(self)? } else { *self };
+        let exponent = u32::try_from(exponent.unsigned_abs()).ok()?;
+        Rational::new(base.numerator.checked_pow(exponent)?, base.denominator.checked_pow(exponent)?)
+    }
+
+    pub fn checked_neg(&self) -> Option<Rational> {
+        Some(Rational { numerator: self.numerator.checked_neg()?, denominator: self.denominator })
+    }
+
+    fn widened(&self, other: &Rational) -> (i128, i128, i128, i128) {
+        (self.numerator as i128, self.denominator as i128, other.numerator as i128, other.denominator as i128)
+    }
+
+    fn reduced(numerator: i128, denominator: i128) -> Option<Rational> {
+        if denominator == 0 {
+            return None;
+        }
+        let (mut a, mut b) = (numerator.abs(), denominator.abs());
+        while b != 0 {
+            (a, b) = (b, a % b);
+        }
+        let sign = if denominator < 0 { -1 } else { 1 };
+        let divisor = a.max(1) * sign;
+        Some(Rational {
+            numerator: i64::try_from(numerator / divisor).ok()?,
+            denominator: i64::try_from(denominator / divisor).ok()?,
+        })
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Rational) -> Ordering {
+        let (a, b, c, d) = self.widened(other);
+        (a * d).cmp(&(c * b))
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Rational) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+//written the way it is typed, 1/3 is read back as the same fraction
+impl Display for Rational {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.numerator)
+        }
+        else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
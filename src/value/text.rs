@@ -0,0 +1,103 @@
+use crate::errors::{Error, ErrorType};
+use crate::value::decimal::{Decimal, Rounding};
+use crate::value::Value;
+
+//largest width and number of decimals of a placeholder
+const MAX_FORMAT_DIGITS: usize = 1000;
+
+//functions building text values
+impl Value {
+    //template with {} replaced by the values in order, {:.2} writes a number with two decimals
+    //and {:8} pads to eight characters, numbers on the left and texts on the right, {{ and }} are braces
+    pub fn format(template: &str, values: &[Value]) -> Result<String, ErrorType> {
+        let mut text = String::new();
+        let mut values = values.iter();
+        let mut chars = template.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.as_str().starts_with('{') => {
+                    chars.next();
+                    text.push('{');
+                }
+                '}' if chars.as_str().starts_with('}') => {
+                    chars.next();
+                    text.push('}');
+                }
+                '{' => {
+                    let rest = chars.as_str();
+                    let Some(end) = rest.find('}') else {
+                        return Error::throw(ErrorType::InvalidOperation);
+                    };
+                    let Some(value) = values.next() else {
+                        return Error::throw(ErrorType::InvalidOperation);
+                    };
+                    text.push_str(&Value::format_value(&rest[..end], value)?);
+                    chars = rest[end + 1..].chars();
+                }
+                '}' => return Error::throw(ErrorType::InvalidOperation),
+                c => text.push(c),
+            }
+        }
+        if values.next().is_some() {
+            return Error::throw(ErrorType::InvalidOperation);
+        }
+        Ok(text)
+    }
+
+    //text as a literal, with the escapes the lexer reads back
+    pub fn quoted(text: &str) -> String {
+        let escaped = text.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n").replace('\t', "\\t");
+        format!("\"{}\"", escaped)
+    }
+
+    //0x1f, negative numbers keep their sign in front
+    pub fn hex(number: i64) -> String {
+        let sign = if number < 0 { "-" } else { "" };
+        format!("{}0x{:x}", sign, number.unsigned_abs())
+    }
+
+    pub fn bin(number: i64) -> String {
+        let sign = if number < 0 { "-" } else { "" };
+        format!("{}0b{:b}", sign, number.unsigned_abs())
+    }
+
+    //value written by a placeholder, spec is what comes between the braces
+    fn format_value(spec: &str, value: &Value) -> Result<String, ErrorType> {
+        let spec = match spec.strip_prefix(':') {
+            Some(spec) => spec,
+            None if spec.is_empty() => spec,
+            None => return Error::throw(ErrorType::InvalidOperation)
+        };
+        let (width, decimals) = spec.split_once('.').unwrap_or((spec, ""));
+        let width = match width {
+            "" => 0,
+            width => match width.parse::<usize>() {
+                Ok(width) if width <= MAX_FORMAT_DIGITS => width,
+                _ => return Error::throw(ErrorType::InvalidOperation)
+            }
+        };
+        let written = match decimals {
+            "" => value.to_string(),
+            decimals => {
+                let decimals = match decimals.parse::<u32>() {
+                    Ok(decimals) if decimals as usize <= MAX_FORMAT_DIGITS => decimals,
+                    _ => return Error::throw(ErrorType::InvalidOperation)
+                };
+                //exact numbers are rounded from their exact value, ties away from zero
+                match (value, value.to_decimal(), value.to_number()) {
+                    (Value::Rational(rational), _, _) => {
+                        let (numerator, denominator) = (Decimal::from_integer(rational.numerator()), Decimal::from_integer(rational.denominator()));
+                        numerator.divide(&denominator, decimals, Rounding::HalfUp)?.to_string()
+                    }
+                    (_, Some(exact), _) => exact.round(decimals, Rounding::HalfUp).to_string(),
+                    (_, None, Some(number)) => format!("{:.*}", decimals as usize, number),
+                    (_, None, None) => return Error::throw(ErrorType::TypeError),
+                }
+            }
+        };
+        Ok(match value {
+            Value::Text(_) => format!("{:<width$}", written),
+            _ => format!("{:>width$}", written),
+        })
+    }
+}
@@ -0,0 +1,219 @@
+use std::fmt::{Display, Formatter};
+
+//exponents of the SI base dimensions: length, mass, time, current, temperature, amount and luminosity
+pub type Dimension = [i32; 7];
+
+const NONE: Dimension = [0, 0, 0, 0, 0, 0, 0];
+const LENGTH: Dimension = [1, 0, 0, 0, 0, 0, 0];
+const MASS: Dimension = [0, 1, 0, 0, 0, 0, 0];
+const TIME: Dimension = [0, 0, 1, 0, 0, 0, 0];
+const CURRENT: Dimension = [0, 0, 0, 1, 0, 0, 0];
+const TEMPERATURE: Dimension = [0, 0, 0, 0, 1, 0, 0];
+const AMOUNT: Dimension = [0, 0, 0, 0, 0, 1, 0];
+const LUMINOSITY: Dimension = [0, 0, 0, 0, 0, 0, 1];
+const VOLUME: Dimension = [3, 0, 0, 0, 0, 0, 0];
+const SPEED: Dimension = [1, 0, -1, 0, 0, 0, 0];
+const FREQUENCY: Dimension = [0, 0, -1, 0, 0, 0, 0];
+const FORCE: Dimension = [1, 1, -2, 0, 0, 0, 0];
+const PRESSURE: Dimension = [-1, 1, -2, 0, 0, 0, 0];
+const ENERGY: Dimension = [2, 1, -2, 0, 0, 0, 0];
+const POWER: Dimension = [2, 1, -3, 0, 0, 0, 0];
+const CHARGE: Dimension = [0, 0, 1, 1, 0, 0, 0];
+const VOLTAGE: Dimension = [2, 1, -3, -1, 0, 0, 0];
+const RESISTANCE: Dimension = [2, 1, -3, -2, 0, 0, 0];
+
+//symbol, value in SI base units, dimension and whether it takes an SI prefix
+const UNITS: [(&str, f64, Dimension, bool); 36] = [
+    ("m", 1.0, LENGTH, true),
+    ("g", 1e-3, MASS, true),
+    ("s", 1.0, TIME, true),
+    ("A", 1.0, CURRENT, true),
+    ("K", 1.0, TEMPERATURE, true),
+    ("mol", 1.0, AMOUNT, true),
+    ("cd", 1.0, LUMINOSITY, true),
+    ("N", 1.0, FORCE, true),
+    ("Pa", 1.0, PRESSURE, true),
+    ("J", 1.0, ENERGY, true),
+    ("W", 1.0, POWER, true),
+    ("Hz", 1.0, FREQUENCY, true),
+    ("C", 1.0, CHARGE, true),
+    ("V", 1.0, VOLTAGE, true),
+    ("ohm", 1.0, RESISTANCE, true),
+    ("Ω", 1.0, RESISTANCE, true),
+    ("L", 1e-3, VOLUME, true),
+    ("Wh", 3600.0, ENERGY, true),
+    ("eV", 1.602176634e-19, ENERGY, true),
+    ("cal", 4.184, ENERGY, true),
+    ("minute", 60.0, TIME, false),
+    ("min", 60.0, TIME, false),
+    ("h", 3600.0, TIME, false),
+    ("day", 86400.0, TIME, false),
+    ("week", 604800.0, TIME, false),
+    ("t", 1000.0, MASS, false),
+    ("bar", 1e5, PRESSURE, false),
+    ("atm", 101325.0, PRESSURE, false),
+    ("inch", 0.0254, LENGTH, false),
+    ("in", 0.0254, LENGTH, false),
+    ("ft", 0.3048, LENGTH, false),
+    ("yd", 0.9144, LENGTH, false),
+    ("mi", 1609.344, LENGTH, false),
+    ("mph", 0.44704, SPEED, false),
+    ("lb", 0.45359237, MASS, false),
+    ("oz", 0.028349523125, MASS, false),
+];
+
+const PREFIXES: [(&str, f64); 21] = [
+    ("Y", 1e24), ("Z", 1e21), ("E", 1e18), ("P", 1e15), ("T", 1e12), ("G", 1e9), ("M", 1e6),
+    ("k", 1e3), ("h", 1e2), ("da", 1e1), ("d", 1e-1), ("c", 1e-2), ("m", 1e-3), ("u", 1e-6),
+    ("µ", 1e-6), ("n", 1e-9), ("p", 1e-12), ("f", 1e-15), ("a", 1e-18), ("z", 1e-21), ("y", 1e-24),
+];
+
+//a product of powers of named units, like km/h which is [("km", 1), ("h", -1)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Unit {
+    factor: f64,
+    dimension: Dimension,
+    symbols: Vec<(String, i32)>,
+}
+
+impl Unit {
+    //the unit of plain numbers
+    pub fn none() -> Unit {
+        Unit { factor: 1.0, dimension: NONE, symbols: Vec::new() }
+    }
+
+    //unit of the registry, possibly with an SI prefix like km or mA, or a plural like days
+    pub fn lookup(name: &str) -> Option<Unit> {
+        let registered = |symbol: &str| UNITS.iter().find(|unit| unit.0 == symbol);
+        let plural = |name: &str| registered(name.strip_suffix('s')?).filter(|unit| unit.0.len() > 2);
+        let (factor, dimension) = match registered(name).or_else(|| plural(name)) {
+            Some((_, factor, dimension, _)) => (*factor, *dimension),
+            None => PREFIXES.iter().find_map(|(prefix, scale)| {
+                match registered(name.strip_prefix(prefix)?) {
+                    Some((_, factor, dimension, true)) => Some((scale * factor, *dimension)),
+                    _ => None
+                }
+            })?
+        };
+        Some(Unit { factor, dimension, symbols: vec![(name.to_string(), 1)] })
+    }
+
+    //units as written after a number, like kg*m/s^2, they keep these symbols rather than being named N
+    pub fn parse(text: &str) -> Option<Unit> {
+        let mut unit = Unit::none();
+        let mut sign = 1;
+        let mut rest = text;
+        loop {
+            let end = rest.find(['*', '/']).unwrap_or(rest.len());
+            let (symbol, power) = match rest[..end].split_once('^') {
+                Some((symbol, power)) => (symbol, power.parse::<i32>().ok()?),
+                None => (&rest[..end], 1),
+            };
+            unit = unit.times(&Unit::lookup(symbol)?.powi(sign * power));
+            if end == rest.len() {
+                return Some(unit);
+            }
+            //a division is by the next unit only, like the denominators of kg*m/s^2/A
+            sign = if rest[end..].starts_with('/') { -1 } else { 1 };
+            rest = &rest[end + 1..];
+        }
+    }
+
+    //value of one of this unit in SI base units
+    pub fn factor(&self) -> f64 {
+        self.factor
+    }
+
+    pub fn dimension(&self) -> Dimension {
+        self.dimension
+    }
+
+    pub fn is_dimensionless(&self) -> bool {
+        self.dimension == NONE
+    }
+
+    pub fn is_time(&self) -> bool {
+        self.dimension == TIME
+    }
+
+    //product of two units, named after a registered unit when there is one, kg*m/s^2 is N
+    pub fn product(&self, other: &Unit) -> Unit {
+        let unit = self.times(other);
+        if self.symbols.is_empty() || other.symbols.is_empty() { unit } else { unit.named() }
+    }
+
+    //product of two units with the symbols of both
+    fn times(&self, other: &Unit) -> Unit {
+        let mut symbols = self.symbols.clone();
+        for (symbol, exponent) in &other.symbols {
+            match symbols.iter_mut().find(|(name, _)| name == symbol) {
+                Some((_, total)) => *total += exponent,
+                None => symbols.push((symbol.clone(), *exponent)),
+            }
+        }
+        symbols.retain(|(_, exponent)| *exponent != 0);
+        let mut dimension = self.dimension;
+        dimension.iter_mut().zip(other.dimension).for_each(|(a, b)| *a += b);
+        Unit { factor: self.factor * other.factor, dimension, symbols }
+    }
+
+    pub fn powi(&self, exponent: i32) -> Unit {
+        Unit {
+            factor: self.factor.powi(exponent),
+            dimension: self.dimension.map(|power| power * exponent),
+            symbols: self.symbols.iter()
+                .map(|(symbol, power)| (symbol.clone(), power * exponent))
+                .filter(|(_, power)| *power != 0)
+                .collect(),
+        }
+    }
+
+    //square root, None unless every exponent is even
+    pub fn sqrt(&self) -> Option<Unit> {
+        if self.symbols.iter().any(|(_, power)| power % 2 != 0) || self.dimension.iter().any(|power| power % 2 != 0) {
+            return None;
+        }
+        Some(Unit {
+            factor: self.factor.sqrt(),
+            dimension: self.dimension.map(|power| power / 2),
+            symbols: self.symbols.iter().map(|(symbol, power)| (symbol.clone(), power / 2)).collect(),
+        })
+    }
+
+    //a single registered unit for a combination of units with the same dimension and value
+    fn named(self) -> Unit {
+        if let [(_, 1)] = self.symbols.as_slice() {
+            return self;
+        }
+        let same = |factor: f64| (factor - self.factor).abs() <= 1e-9 * self.factor.abs();
+        match UNITS.iter().find(|(_, factor, dimension, _)| *dimension == self.dimension && same(*factor)) {
+            Some((symbol, ..)) => Unit { symbols: vec![(symbol.to_string(), 1)], ..self },
+            None => self
+        }
+    }
+}
+
+//written as a product of the units over a product of the units with a negative exponent, like kg*m/s^2
+//a unit with only negative exponents is written with them, like s^-1
+impl Display for Unit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let symbol = |(name, power): (&String, i32)| match power {
+            1 => name.clone(),
+            power => format!("{}^{}", name, power),
+        };
+        let numerator: Vec<String> = self.symbols.iter()
+            .filter(|(_, power)| *power > 0)
+            .map(|(name, power)| symbol((name, *power)))
+            .collect();
+        let denominator = self.symbols.iter().filter(|(_, power)| *power < 0);
+        if numerator.is_empty() {
+            let symbols: Vec<String> = denominator.map(|(name, power)| symbol((name, *power))).collect();
+            return write!(f, "{}", symbols.join("*"));
+        }
+        write!(f, "{}", numerator.join("*"))?;
+        for (name, power) in denominator {
+            write!(f, "/{}", symbol((name, -power)))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,48 @@
+mod common;
+
+use common::evaluate;
+
+#[test]
+fn assignment_yields_its_value() {
+    assert_eq!(evaluate("a=5\n"), ("5".to_string(), 0));
+    assert_eq!(evaluate("a=2+3*4\n"), ("14".to_string(), 0));
+}
+
+#[test]
+fn chained_assignment_is_right_associative() {
+    assert_eq!(evaluate("a=b=3;a+b\n"), ("6".to_string(), 0));
+    assert_eq!(evaluate("x=y=(z=2)*3;x+y+z\n"), ("14".to_string(), 0));
+}
+
+#[test]
+fn nested_assignment_inside_expression() {
+    assert_eq!(evaluate("x=(y=2)+1;x*10+y\n"), ("32".to_string(), 0));
+    assert_eq!(evaluate("1+(y=2)\n"), ("3".to_string(), 0));
+    assert_eq!(evaluate("max(a=1, 2)+a\n"), ("3".to_string(), 0));
+}
+
+#[test]
+fn function_arguments_are_complete_expressions() {
+    assert_eq!(evaluate("max(1+2, 3*4)\n"), ("12".to_string(), 0));
+}
+
+#[test]
+fn function_names_are_variables_unless_called() {
+    assert_eq!(evaluate("re = 2; re + 1\n").0, "3");
+    assert_eq!(evaluate("sum = 3; pv = 4; sum * pv\n").0, "12");
+    assert_eq!(evaluate("mode = [1, 2, 2]; mode(mode)\n").0, "2");
+    assert_eq!(evaluate("str = 5; str(str)\n").0, "5");
+    assert_eq!(evaluate("let im = 2 in im ^ 2\n").0, "4");
+}
+
+#[test]
+fn invalid_assignment_target() {
+    assert_eq!(evaluate("3=4\n").1, 2);
+    assert_eq!(evaluate("2*a=3\n").1, 2);
+    assert_eq!(evaluate("2a=3\n").1, 2);
+}
+
+#[test]
+fn assignment_from_unbound_variable() {
+    assert_eq!(evaluate("a=b\n").1, 3);
+}
@@ -0,0 +1,38 @@
+#![allow(dead_code)]
+
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+//run the calculator in silent mode and return (stdout, exit code)
+pub fn evaluate(input: &str) -> (String, i32) {
+    evaluate_with(&[], input)
+}
+
+//same as evaluate with more command line flags, e.g. --complex or --precision 2
+pub fn evaluate_with(flags: &[&str], input: &str) -> (String, i32) {
+    let output = run(flags, input);
+    (String::from_utf8(output.stdout).unwrap().trim().to_string(), output.status.code().unwrap())
+}
+
+//message of a failed evaluation, e.g. "Shape mismatch."
+pub fn error(input: &str) -> String {
+    error_with(&[], input)
+}
+
+pub fn error_with(flags: &[&str], input: &str) -> String {
+    String::from_utf8(run(flags, input).stderr).unwrap().trim().to_string()
+}
+
+fn run(flags: &[&str], input: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_rust_abacus"))
+        .arg("-silent")
+        .args(flags)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to start rust_abacus");
+    //a run which fails before reading its input closes it, which is not an error of the test
+    let _ = child.stdin.take().unwrap().write_all(input.as_bytes());
+    child.wait_with_output().unwrap()
+}
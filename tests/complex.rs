@@ -0,0 +1,60 @@
+mod common;
+
+use common::{error, error_with, evaluate, evaluate_with};
+
+fn complex(input: &str) -> String {
+    evaluate_with(&["--complex"], input).0
+}
+
+#[test]
+fn imaginary_unit_and_roots_of_negatives() {
+    assert_eq!(evaluate_with(&["--complex"], "sqrt(0-4)\n"), ("2i".to_string(), 0));
+    assert_eq!(complex("i^2\n"), "-1");
+    assert_eq!(complex("ln(0-1)\n"), "3.141592653589793i");
+    assert_eq!(complex("(0-8)^(1/3)\n"), "1 + 1.732050807568877i");
+}
+
+#[test]
+fn arithmetic() {
+    assert_eq!(complex("(1 + 2*i) * (3 - i)\n"), "5 + 5i");
+    assert_eq!(complex("(1 + 2*i) / (1 - i)\n"), "-0.5 + 1.5i");
+    assert_eq!(complex("sqrt(i)\n"), "0.7071067811865476 + 0.7071067811865476i");
+}
+
+#[test]
+fn parts() {
+    assert_eq!(complex("abs(3 + 4*i)\n"), "5");
+    assert_eq!(complex("arg(i)\n"), "1.5707963267948966");
+    assert_eq!(complex("conj(1 + i)\n"), "1 - i");
+    assert_eq!(complex("re(2 + 3*i)\n"), "2");
+    assert_eq!(complex("im(2 + 3*i)\n"), "3");
+}
+
+#[test]
+fn complex_function_works_in_any_mode() {
+    assert_eq!(evaluate("complex(1, 2) * complex(1, 2)\n").0, "-3 + 4i");
+    assert_eq!(evaluate("abs(complex(3, 4))\n").0, "5");
+    assert_eq!(evaluate("complex(1, 0)\n").0, "1");
+}
+
+#[test]
+fn real_mode_has_no_imaginary_unit() {
+    assert_eq!(error("sqrt(0-4)\n"), "Invalid operation.");
+    assert_eq!(error("i\n"), "Unbound variable.");
+}
+
+#[test]
+fn fractional_powers_of_negatives_need_complex_mode() {
+    assert_eq!(error("(-2)^0.5\n"), "Invalid operation.");
+    assert_eq!(error("(-8)^(1/3)\n"), "Invalid operation.");
+    assert_eq!(error_with(&["--precision", "50"], "(-1.5)^0.5\n"), "Invalid operation.");
+    assert_eq!(evaluate("(-2)^2.0\n").0, "4");
+    assert_eq!(complex("(-2)^0.5\n"), "1.414213562373095i");
+}
+
+#[test]
+fn errors() {
+    assert_eq!(error_with(&["--complex"], "ln(0)\n"), "Invalid operation.");
+    assert_eq!(error_with(&["--complex"], "i + \"a\"\n"), "Type error.");
+    assert_eq!(error("complex(\"a\", 1)\n"), "Type error.");
+}
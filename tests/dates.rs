@@ -0,0 +1,49 @@
+mod common;
+
+use common::{error, evaluate};
+
+#[test]
+fn durations_move_a_date() {
+    assert_eq!(evaluate("2026-10-18 + 90 days\n"), ("2027-01-16".to_string(), 0));
+    assert_eq!(evaluate("2024-02-29 + 1 day\n").0, "2024-03-01");
+    assert_eq!(evaluate("2026-10-18 - 1 week\n").0, "2026-10-11");
+    assert_eq!(evaluate("2026-10-18 + 2 h\n").0, "2026-10-18T02:00:00");
+    assert_eq!(evaluate("2026-10-18 + 1.5 days\n").0, "2026-10-19T12:00:00");
+}
+
+#[test]
+fn differences_are_in_days() {
+    assert_eq!(evaluate("2026-10-18 - 2026-01-01\n").0, "290 days");
+    assert_eq!(evaluate("2026-01-01 - 2026-10-18\n").0, "-290 days");
+    assert_eq!(evaluate("2027-10-18 - 2026-10-18\n").0, "365 days");
+    assert_eq!(evaluate("2026-10-19 - 2026-10-18\n").0, "1 day");
+    assert_eq!(evaluate("2026-10-19T12:00 - 2026-10-18\n").0, "1.5 days");
+    assert_eq!(evaluate("(now - 2026-01-01) > 0 days\n").0, "true");
+}
+
+#[test]
+fn weekdays_and_iso_weeks() {
+    assert_eq!(evaluate("weekday(2026-10-18)\n").0, "7");
+    assert_eq!(evaluate("weekday(2026-10-19)\n").0, "1");
+    assert_eq!(evaluate("isoweek(2026-10-18)\n").0, "42");
+    assert_eq!(evaluate("isoweek(2021-01-03)\n").0, "53");
+}
+
+#[test]
+fn dates_compare() {
+    assert_eq!(evaluate("2026-10-18 < 2026-10-19\n").0, "true");
+    assert_eq!(evaluate("2026-10-18 == 2026-10-18\n").0, "true");
+    assert_eq!(evaluate("max(2026-10-18, 2025-01-01)\n").0, "2026-10-18");
+    assert_eq!(evaluate("min(2026-10-18, 2025-01-01)\n").0, "2025-01-01");
+}
+
+#[test]
+fn invalid_dates_and_operations() {
+    assert_eq!(error("2026-02-30\n"), "Syntax error.");
+    assert_eq!(error("2026-10-18 + 1\n"), "Dimension mismatch.");
+    assert_eq!(error("2026-10-18 + 1 kg\n"), "Dimension mismatch.");
+    assert_eq!(error("2026-10-18 * 2\n"), "Invalid operation.");
+    assert_eq!(error("weekday(1)\n"), "Invalid operation.");
+    assert_eq!(error("weekday(\"a\")\n"), "Type error.");
+    assert_eq!(error("2026-10-18 + \"a\"\n"), "Type error.");
+}
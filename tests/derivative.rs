@@ -0,0 +1,59 @@
+mod common;
+
+use common::evaluate;
+
+#[test]
+fn derivative_of_a_product() {
+    assert_eq!(evaluate("diff(x^2 * sin(x), x)\n"), ("2 * x * sin(x) + x^2 * cos(x)".to_string(), 0));
+}
+
+#[test]
+fn derivatives_of_operators() {
+    assert_eq!(evaluate("diff(x^3, x)\n").0, "3 * x^2");
+    assert_eq!(evaluate("diff(x/(x+1), x)\n").0, "(x + 1 - x) / (x + 1)^2");
+    assert_eq!(evaluate("diff(2^x, x)\n").0, "2^x * ln(2)");
+    assert_eq!(evaluate("diff(x % 2, x)\n").0, "1");
+    assert_eq!(evaluate("diff(3, x)\n").0, "0");
+}
+
+#[test]
+fn derivatives_of_functions() {
+    assert_eq!(evaluate("diff(ln(x), x)\n").0, "1 / x");
+    assert_eq!(evaluate("diff(sqrt(x), x)\n").0, "1 / (2 * sqrt(x))");
+    assert_eq!(evaluate("diff(exp(2*x), x)\n").0, "2 * exp(2 * x)");
+    assert_eq!(evaluate("diff(cos(x), x)\n").0, "-sin(x)");
+    assert_eq!(evaluate("diff(abs(x), x)\n").0, "sign(x)");
+    assert_eq!(evaluate("diff(abs(2*x), x)\n").0, "2 * sign(2 * x)");
+}
+
+#[test]
+fn sign_of_numbers() {
+    assert_eq!(evaluate("[sign(-3), sign(0), sign(2.5)]\n").0, "[-1, 0, 1]");
+    assert_eq!(evaluate("diff(sign(x), x)\n").0, "0");
+}
+
+#[test]
+fn bound_names_are_substituted_except_the_variable() {
+    assert_eq!(evaluate("y = 2; diff(x*y, x)\n").0, "2");
+    assert_eq!(evaluate("x = 3; diff(x^2, x)\n").0, "2 * x");
+}
+
+#[test]
+fn nested_derivative() {
+    assert_eq!(evaluate("diff(diff(x^3, x), x)\n").0, "6 * x");
+}
+
+#[test]
+fn function_values_are_real() {
+    assert_eq!(evaluate("sin(0) + cos(0)\n").0, "1");
+    assert_eq!(evaluate("exp(1)\n").0, "2.718281828459045");
+    assert_eq!(evaluate("ln(1)\n").0, "0");
+}
+
+#[test]
+fn invalid_derivatives() {
+    assert_eq!(evaluate("diff(x^2, 2)\n").1, 2);
+    assert_eq!(evaluate("diff(facto(x), x)\n").1, 3);
+    assert_eq!(evaluate("diff(x % x, x)\n").1, 3);
+    assert_eq!(evaluate("ln(0)\n").1, 3);
+}
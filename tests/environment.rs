@@ -0,0 +1,59 @@
+mod common;
+
+use common::{evaluate, evaluate_with};
+use rust_abacus::errors::ErrorType;
+use rust_abacus::executor::environment::Environment;
+use rust_abacus::executor::Executor;
+use rust_abacus::value::Value;
+
+#[test]
+fn variables_are_read_back() {
+    assert_eq!(evaluate("a = 2; b = a * 3; a + b\n"), ("8".to_string(), 0));
+    assert_eq!(evaluate("a = 2; a = a + 1; a\n"), ("3".to_string(), 0));
+}
+
+#[test]
+fn failed_line_is_undone_in_the_repl() {
+    let (output, code) = evaluate_with(&["-repl"], "x = 2\nx = 5 + 1/0\nx\n");
+    assert_eq!(output, "2\n2");
+    assert_eq!(code, 0);
+}
+
+#[test]
+fn preloaded_variables_and_final_bindings() {
+    let mut executor = Executor::with_vars([("rate".to_string(), Value::Integer(3))]);
+    assert_eq!(executor.evaluate("total = rate * 4").unwrap(), Value::Integer(12));
+    let bindings = executor.vars.bindings();
+    assert_eq!(bindings.get("rate"), Some(&Value::Integer(3)));
+    assert_eq!(bindings.get("total"), Some(&Value::Integer(12)));
+}
+
+#[test]
+fn restore_goes_back_to_the_snapshot() {
+    let mut vars = Environment::new();
+    vars.assign("x", Value::Integer(1));
+    let snapshot = vars.snapshot();
+    vars.assign("x", Value::Integer(2));
+    vars.assign("y", Value::Integer(3));
+    vars.restore(snapshot);
+    assert_eq!(vars.get("x"), Some(Value::Integer(1)));
+    assert_eq!(vars.get("y"), None);
+}
+
+#[test]
+fn scopes_shadow_and_end() {
+    let mut vars = Environment::new();
+    vars.assign("x", Value::Integer(1));
+    vars.push_scope();
+    vars.define("x", Value::Integer(2));
+    assert_eq!(vars.get("x"), Some(Value::Integer(2)));
+    vars.pop_scope();
+    assert_eq!(vars.get("x"), Some(Value::Integer(1)));
+}
+
+#[test]
+fn failed_evaluation_leaves_no_local_binding() {
+    let mut executor = Executor::new();
+    assert_eq!(executor.evaluate("let x = 1 in x / 0"), Err(ErrorType::InvalidOperation));
+    assert_eq!(executor.evaluate("x"), Err(ErrorType::UnboundVariable));
+}
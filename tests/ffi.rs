@@ -0,0 +1,158 @@
+use std::ffi::{c_char, CStr, CString};
+use std::path::Path;
+use std::process::Command;
+use std::ptr;
+use rust_abacus::executor::Executor;
+use rust_abacus::ffi::*;
+
+//text written by a call, released like a C caller would
+unsafe fn taken(text: *mut c_char) -> Option<String> {
+    if text.is_null() {
+        return None;
+    }
+    let owned = CStr::from_ptr(text).to_str().unwrap().to_string();
+    abacus_free_string(text);
+    Some(owned)
+}
+
+unsafe fn last_error(executor: *const AbacusExecutor) -> String {
+    CStr::from_ptr(abacus_last_error(executor)).to_str().unwrap().to_string()
+}
+
+unsafe fn evaluated(executor: *mut AbacusExecutor, input: &str) -> (i32, Option<String>) {
+    let input = CString::new(input).unwrap();
+    let mut result = ptr::null_mut();
+    let status = abacus_evaluate(executor, input.as_ptr(), &mut result);
+    (status, taken(result))
+}
+
+unsafe fn set(executor: *mut AbacusExecutor, name: &str, value: &str) -> i32 {
+    let (name, value) = (CString::new(name).unwrap(), CString::new(value).unwrap());
+    abacus_set_variable(executor, name.as_ptr(), value.as_ptr())
+}
+
+unsafe fn get(executor: *mut AbacusExecutor, name: &str) -> (i32, Option<String>) {
+    let name = CString::new(name).unwrap();
+    let mut value = ptr::null_mut();
+    let status = abacus_get_variable(executor, name.as_ptr(), &mut value);
+    (status, taken(value))
+}
+
+#[test]
+fn evaluate_returns_the_value_as_text() {
+    unsafe {
+        let executor = abacus_create();
+        assert_eq!(evaluated(executor, "1/3 + 1"), (ABACUS_OK, Some("4/3".to_string())));
+        assert_eq!(evaluated(executor, "a = 2; a * 3"), (ABACUS_OK, Some("6".to_string())));
+        assert_eq!(last_error(executor), "");
+        assert_eq!(abacus_evaluate(executor, c"a + 1".as_ptr(), ptr::null_mut()), ABACUS_OK);
+        abacus_free(executor);
+    }
+}
+
+#[test]
+fn errors_are_statuses_and_messages() {
+    unsafe {
+        let executor = abacus_create();
+        assert_eq!(evaluated(executor, "1 +"), (ABACUS_SYNTAX_ERROR, None));
+        assert_eq!(last_error(executor), "Syntax error.");
+        assert_eq!(evaluated(executor, "1 / 0").0, ABACUS_INVALID_OPERATION);
+        assert_eq!(evaluated(executor, "y").0, ABACUS_UNBOUND_VARIABLE);
+        assert_eq!(evaluated(executor, "[1, 2] + [1, 2, 3]").0, ABACUS_SHAPE_MISMATCH);
+        assert_eq!(evaluated(executor, "1 km + 1 s").0, ABACUS_DIMENSION_MISMATCH);
+        assert_eq!(evaluated(executor, "\"a\" * 2").0, ABACUS_TYPE_ERROR);
+        assert_eq!(last_error(executor), "Type error.");
+        assert_eq!(evaluated(executor, "2").0, ABACUS_OK);
+        assert_eq!(last_error(executor), "");
+        abacus_free(executor);
+    }
+}
+
+#[test]
+fn failed_calls_leave_the_variables() {
+    unsafe {
+        let executor = abacus_create();
+        assert_eq!(set(executor, "x", "1"), ABACUS_OK);
+        assert_eq!(evaluated(executor, "x = 5; y = 1 / 0").0, ABACUS_INVALID_OPERATION);
+        assert_eq!(get(executor, "x"), (ABACUS_OK, Some("1".to_string())));
+        assert_eq!(get(executor, "y").0, ABACUS_UNBOUND_VARIABLE);
+        abacus_free(executor);
+    }
+}
+
+#[test]
+fn variables_are_set_and_read() {
+    unsafe {
+        let executor = abacus_create();
+        assert_eq!(set(executor, "price", "120"), ABACUS_OK);
+        assert_eq!(set(executor, "label", "\"total\""), ABACUS_OK);
+        assert_eq!(set(executor, "share", "price / 360"), ABACUS_OK);
+        assert_eq!(set(executor, "local", "let a = 2 in a * a"), ABACUS_OK);
+        assert_eq!(evaluated(executor, "price * 1.5"), (ABACUS_OK, Some("180.0".to_string())));
+        assert_eq!(get(executor, "label"), (ABACUS_OK, Some("total".to_string())));
+        assert_eq!(get(executor, "share"), (ABACUS_OK, Some("1/3".to_string())));
+        assert_eq!(get(executor, "local"), (ABACUS_OK, Some("4".to_string())));
+        assert_eq!(set(executor, "sqrt", "9"), ABACUS_OK);
+        assert_eq!(evaluated(executor, "sqrt(sqrt)"), (ABACUS_OK, Some("3".to_string())));
+        abacus_free(executor);
+    }
+}
+
+#[test]
+fn set_variable_takes_a_single_expression() {
+    unsafe {
+        let executor = abacus_create();
+        assert_eq!(set(executor, "x", "1; 2"), ABACUS_SYNTAX_ERROR);
+        assert_eq!(set(executor, "x", "y = 2"), ABACUS_SYNTAX_ERROR);
+        assert_eq!(set(executor, "x", "(y = 2) + 1"), ABACUS_SYNTAX_ERROR);
+        assert_eq!(set(executor, "x", ""), ABACUS_SYNTAX_ERROR);
+        assert_eq!(last_error(executor), "Syntax error.");
+        assert_eq!(get(executor, "x").0, ABACUS_UNBOUND_VARIABLE);
+        assert_eq!(get(executor, "y").0, ABACUS_UNBOUND_VARIABLE);
+        assert_eq!(set(executor, "x", "2;"), ABACUS_OK);
+        abacus_free(executor);
+    }
+}
+
+#[test]
+fn invalid_arguments_are_refused() {
+    unsafe {
+        let executor = abacus_create();
+        assert_eq!(abacus_evaluate(ptr::null_mut(), c"1".as_ptr(), ptr::null_mut()), ABACUS_INVALID_ARGUMENT);
+        assert_eq!(abacus_evaluate(executor, ptr::null(), ptr::null_mut()), ABACUS_INVALID_ARGUMENT);
+        assert_eq!(last_error(executor), "Invalid argument: input.");
+        assert_eq!(set(executor, "to", "1"), ABACUS_INVALID_ARGUMENT);
+        assert_eq!(set(executor, "let", "1"), ABACUS_INVALID_ARGUMENT);
+        assert_eq!(set(executor, "a b", "1"), ABACUS_INVALID_ARGUMENT);
+        assert_eq!(abacus_set_variable(executor, c"x".as_ptr(), ptr::null()), ABACUS_INVALID_ARGUMENT);
+        assert_eq!(abacus_get_variable(executor, ptr::null(), ptr::null_mut()), ABACUS_INVALID_ARGUMENT);
+        assert_eq!(CStr::from_ptr(abacus_last_error(ptr::null())).to_str().unwrap(), "Invalid argument: executor.");
+        abacus_free(executor);
+        abacus_free(ptr::null_mut());
+        abacus_free_string(ptr::null_mut());
+    }
+}
+
+#[test]
+fn history_is_only_the_last_evaluation() {
+    let mut executor = Executor::new();
+    executor.evaluate("1 + 2").unwrap();
+    let length = executor.token_history.len();
+    for _ in 0..10 {
+        executor.evaluate("1 + 2").unwrap();
+    }
+    assert_eq!(executor.token_history.len(), length);
+}
+
+#[test]
+fn the_header_compiles() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    for language in ["c", "c++"] {
+        let status = Command::new("cc")
+            .args(["-fsyntax-only", "-Wall", "-Werror", "-x", language])
+            .arg(root.join("include").join("abacus.h"))
+            .status()
+            .expect("Failed to run cc");
+        assert!(status.success(), "{}", language);
+    }
+}
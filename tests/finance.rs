@@ -0,0 +1,54 @@
+mod common;
+
+use common::{error, evaluate, evaluate_with};
+
+#[test]
+fn money_is_decimal() {
+    assert_eq!(evaluate("fv(0.05, 3, 100)\n"), ("315.25".to_string(), 0));
+    assert_eq!(evaluate("pv(0.1, 2, 121)\n").0, "210");
+    assert_eq!(evaluate("compound(100, 0.1, 2)\n").0, "121");
+    assert_eq!(evaluate("npv(0.5, [-100, 150])\n").0, "0");
+    assert_eq!(evaluate("irr([-100, 110])\n").0, "0.1");
+}
+
+#[test]
+fn inexact_amounts_have_sixteen_digits() {
+    assert_eq!(evaluate("pmt(0.01, 12, 1000)\n").0, "88.8487886783417073");
+    assert_eq!(evaluate("pv(0.05, 3, 100)\n").0, "272.324802937047835");
+    assert_eq!(evaluate("npv(0.1, [-100, 50, 60])\n").0, "-4.9586776859504132");
+    assert_eq!(evaluate("irr([-100, 50, 60])\n").0, "0.0639410298049853");
+    assert_eq!(evaluate("pmt(0.01, 2.5, 100)\n").0, "40.701741287067311");
+}
+
+#[test]
+fn zero_rates() {
+    assert_eq!(evaluate("pmt(0, 12, 1200)\n").0, "100");
+    assert_eq!(evaluate("fv(0, 12, 100)\n").0, "1200");
+}
+
+#[test]
+fn precision_rounds_amounts() {
+    assert_eq!(evaluate_with(&["--precision", "2"], "pmt(0.01, 12, 1000)\n").0, "88.85");
+    assert_eq!(evaluate_with(&["--precision", "2", "--rounding", "floor"], "pmt(0.01, 12, 1000)\n").0, "88.84");
+    assert_eq!(evaluate("round(pmt(0.01, 12, 1000), 2)\n").0, "88.85");
+}
+
+#[test]
+fn amortization_schedule() {
+    assert_eq!(evaluate("amortize(0.01, 3, 1000)\n").0,
+        "[[1, 340.02, 10.00, 330.02, 669.98], [2, 340.02, 6.70, 333.32, 336.66], [3, 340.03, 3.37, 336.66, 0.00]]");
+    assert_eq!(evaluate("a = amortize(0.01, 3, 1000); a[3, 2]\n").0, "340.03");
+}
+
+#[test]
+fn errors() {
+    assert_eq!(error("pmt(0.01, 0, 100)\n"), "Invalid operation.");
+    assert_eq!(error("npv(-1, [1])\n"), "Invalid operation.");
+    assert_eq!(error("irr([100, 50])\n"), "Invalid operation.");
+    assert_eq!(error("irr([])\n"), "Invalid operation.");
+    assert_eq!(error("irr([0, 0])\n"), "Invalid operation.");
+    assert_eq!(error("irr([-100, 0])\n"), "Invalid operation.");
+    assert_eq!(error("amortize(0.01, 2.5, 100)\n"), "Invalid operation.");
+    assert_eq!(error("npv(0.1, 3)\n"), "Invalid operation.");
+    assert_eq!(error("pmt(\"a\", 1, 1)\n"), "Type error.");
+}
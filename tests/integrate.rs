@@ -0,0 +1,54 @@
+mod common;
+
+use common::evaluate;
+
+#[test]
+fn integrals() {
+    assert_eq!(evaluate("integrate(x^2, x, 0, 3)\n"), ("9".to_string(), 0));
+    assert_eq!(evaluate("integrate(x*x, x, 0, 1)\n").0, "0.3333333333333333");
+    assert_eq!(evaluate("integrate(exp(0-x^2), x, -10, 10)\n").0, "1.772453850905516");
+    assert_eq!(evaluate("integrate(x^2, x, 0, 3) + 1\n").0, "10");
+}
+
+#[test]
+fn reversed_bounds_change_the_sign() {
+    assert_eq!(evaluate("integrate(x, x, 2, 0)\n").0, "-2");
+}
+
+#[test]
+fn bounds_and_bodies_use_variables() {
+    assert_eq!(evaluate("a = 1; integrate(x, x, a, a + 1)\n").0, "1.5");
+    assert_eq!(evaluate("y = 2; integrate(y*x, x, 0, 1)\n").0, "1");
+    assert_eq!(evaluate("integrate(y*x, x, 0, 1)\n").1, 3);
+}
+
+#[test]
+fn the_variable_is_bound_only_inside() {
+    assert_eq!(evaluate("integrate(x, x, 0, 1); x\n").1, 3);
+    assert_eq!(evaluate("x = 5; root(x - 1, x, 0, 2); x\n").0, "5");
+}
+
+#[test]
+fn roots() {
+    assert_eq!(evaluate("root(x^2 - 2, x, 0, 2)\n"), ("1.414213562373095".to_string(), 0));
+    assert_eq!(evaluate("root(cos(x) - x, x, 0, 1)\n").0, "0.7390851332151607");
+    assert_eq!(evaluate("root(x^3 - 8, x, 0, 10)\n").0, "2");
+}
+
+#[test]
+fn small_results_are_not_rounded_to_zero() {
+    assert_eq!(evaluate("integrate(0.0000000001, x, 0, 1)\n").0, "0.0000000001");
+    assert_eq!(evaluate("root(x - 0.0000000001, x, -1, 1)\n").0, "0.0000000001");
+    assert_eq!(evaluate("integrate(sin(x), x, 0, 2*3.141592653589793)\n").0, "0");
+    assert_eq!(evaluate("root(x^3, x, -1, 2)\n").0, "0");
+}
+
+#[test]
+fn errors() {
+    assert_eq!(evaluate("integrate(1/x, x, -1, 1)\n").1, 3);
+    assert_eq!(evaluate("root(x^2 + 1, x, 0, 2)\n").1, 3);
+    assert_eq!(evaluate("root(1/x, x, -1, 1)\n").1, 3);
+    assert_eq!(evaluate("integrate(x, x, 0)\n").1, 3);
+    assert_eq!(evaluate("root(x - 1, x, 0)\n").1, 3);
+    assert_eq!(evaluate("integrate(x, 3, 0, 1)\n").1, 2);
+}
@@ -0,0 +1,56 @@
+mod common;
+
+use common::{error_with, evaluate, evaluate_with};
+
+fn interval(input: &str) -> (String, i32) {
+    evaluate_with(&["--interval"], &format!("{}\n", input))
+}
+
+#[test]
+fn exact_bounds_are_not_widened() {
+    assert_eq!(interval("2 ± 0.1"), ("[1.9, 2.1]".to_string(), 0));
+    assert_eq!(interval("[1, 2] + [3, 4]").0, "[4, 6]");
+    assert_eq!(interval("[1, 2] * [-3, 4]").0, "[-6, 8]");
+    assert_eq!(interval("[1, 2] / [4, 8]").0, "[0.125, 0.5]");
+    assert_eq!(interval("[-2, 3]^2").0, "[0, 9]");
+    assert_eq!(interval("sqrt([4, 9])").0, "[2, 3]");
+    assert_eq!(interval("[5, 6] % 4").0, "[1, 2]");
+}
+
+#[test]
+fn inexact_bounds_are_rounded_outwards() {
+    assert_eq!(interval("[1.9, 2.1] * [2.9, 3.1]").0, "[5.509999999999999, 6.510000000000001]");
+    assert_eq!(interval("1 / [3, 3]").0, "[0.3333333333333333, 0.33333333333333337]");
+    assert_eq!(interval("sqrt([2, 2])").0, "[1.414213562373095, 1.4142135623730951]");
+    assert_eq!(interval("[-2.1, -1.9]^3").0, "[-9.261000000000003, -6.858999999999998]");
+}
+
+#[test]
+fn results_hold_the_exact_value() {
+    assert_eq!(interval("(1 / [3, 3]) * 3").0, "[0.9999999999999999, 1.0000000000000002]");
+    assert_eq!(interval("x = 0.1 ± 0; x + x + x").0, "[0.3, 0.30000000000000004]");
+}
+
+#[test]
+fn monotone_functions() {
+    assert_eq!(interval("abs([-3, 2])").0, "[0, 3]");
+    assert_eq!(interval("max([1, 4], [2, 3])").0, "[2, 4]");
+    assert_eq!(interval("ln(exp([1, 2]))").0, "[0.9999999999999997, 2.0000000000000004]");
+}
+
+#[test]
+fn brackets_are_lists_without_the_flag() {
+    assert_eq!(interval("[1.9, 2.1] * [2.9, 3.1]").0, "[5.509999999999999, 6.510000000000001]");
+    assert_eq!(evaluate("[1.9, 2.1] * [2.9, 3.1]\n").0, "[5.51, 6.51]");
+    assert!(evaluate_with(&["--help"], "").0.contains("--interval"));
+}
+
+#[test]
+fn invalid_intervals() {
+    assert_eq!(error_with(&["--interval"], "1 / [-1, 1]\n"), "Invalid operation.");
+    assert_eq!(error_with(&["--interval"], "[2, 1]\n"), "Invalid operation.");
+    assert_eq!(error_with(&["--interval"], "2 ± (0 - 1)\n"), "Invalid operation.");
+    assert_eq!(error_with(&["--interval"], "sqrt([-1, 1])\n"), "Invalid operation.");
+    assert_eq!(error_with(&["--interval"], "[-1, 1]^0.5\n"), "Invalid operation.");
+    assert_eq!(error_with(&["--interval"], "[1, 2] + \"a\"\n"), "Type error.");
+}
@@ -0,0 +1,36 @@
+mod common;
+
+use common::evaluate;
+
+#[test]
+fn let_binds_names_for_its_body() {
+    assert_eq!(evaluate("let x = 2 in x * 3\n"), ("6".to_string(), 0));
+    assert_eq!(evaluate("let x = 1, y = x + 1 in x * 10 + y\n"), ("12".to_string(), 0));
+}
+
+#[test]
+fn let_bindings_are_local() {
+    assert_eq!(evaluate("let x = 2 in x; x\n").1, 3);
+    assert_eq!(evaluate("x = 5; (let x = 1 in x) + x\n"), ("6".to_string(), 0));
+}
+
+#[test]
+fn nested_let() {
+    assert_eq!(evaluate("let a = let b = 2 in b * 2 in a + 1\n"), ("5".to_string(), 0));
+    assert_eq!(evaluate("max(let a = 1, b = 2 in a + b, 0)\n"), ("3".to_string(), 0));
+}
+
+#[test]
+fn let_without_bindings() {
+    assert_eq!(evaluate("let in 3\n").1, 2);
+    assert_eq!(evaluate("let x in 3\n").1, 2);
+    assert_eq!(evaluate("let x = 1, 2 in x\n").1, 2);
+    assert_eq!(evaluate("let x = 1, in 2\n").1, 2);
+}
+
+#[test]
+fn let_without_body() {
+    assert_eq!(evaluate("let x = 1 in\n").1, 2);
+    assert_eq!(evaluate("(let x = 1 in) + 1\n").1, 2);
+    assert_eq!(evaluate("let x = 1\n").1, 2);
+}
@@ -0,0 +1,47 @@
+mod common;
+
+use common::{error, evaluate};
+
+#[test]
+fn lists_and_ranges() {
+    assert_eq!(evaluate("[1, 2, 3]\n"), ("[1, 2, 3]".to_string(), 0));
+    assert_eq!(evaluate("1..5\n").0, "[1, 2, 3, 4, 5]");
+    assert_eq!(evaluate("5..1\n").0, "[]");
+    assert_eq!(evaluate("[]\n").0, "[]");
+}
+
+#[test]
+fn indexing_starts_at_one() {
+    assert_eq!(evaluate("xs = [10, 20, 30]; xs[2]\n").0, "20");
+    assert_eq!(evaluate("[1,2][1]\n").0, "1");
+    assert_eq!(evaluate("xs = [10, 20, 30]; xs[0]\n").1, 3);
+    assert_eq!(evaluate("xs = [10, 20, 30]; xs[4]\n").1, 3);
+    assert_eq!(evaluate("xs = [1,2]; xs[1.5]\n").1, 3);
+}
+
+#[test]
+fn length() {
+    assert_eq!(evaluate("len([1,2,3])\n").0, "3");
+    assert_eq!(evaluate("len(1..10)\n").0, "10");
+    assert_eq!(evaluate("len([])\n").0, "0");
+}
+
+#[test]
+fn element_wise_operations() {
+    assert_eq!(evaluate("sqrt([4, 9, 16])\n").0, "[2, 3, 4]");
+    assert_eq!(evaluate("abs([0-1, 2])\n").0, "[1, 2]");
+    assert_eq!(evaluate("[1,2,3] * 2\n").0, "[2, 4, 6]");
+    assert_eq!(evaluate("2 - [1,2]\n").0, "[1, 0]");
+    assert_eq!(evaluate("[1,2] + [3,4]\n").0, "[4, 6]");
+    assert_eq!(evaluate("[1,2] ^ 2\n").0, "[1, 4]");
+    assert_eq!(evaluate("-[1,2]\n").0, "[-1, -2]");
+}
+
+#[test]
+fn errors() {
+    assert_eq!(evaluate("[1,2] + [1,2,3]\n"), (String::new(), 3));
+    assert_eq!(error("[1,2] + [1,2,3]\n"), "Shape mismatch.");
+    assert_eq!(error("[[1,2],[3]]\n"), "Shape mismatch.");
+    assert_eq!(error("1..2.5\n"), "Invalid operation.");
+    assert_eq!(error("[1,2] == [1,2]\n"), "Type error.");
+}
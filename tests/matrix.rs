@@ -0,0 +1,58 @@
+mod common;
+
+use common::{error, evaluate};
+
+#[test]
+fn rows_of_numbers_make_a_matrix() {
+    assert_eq!(evaluate("[[1,2],[3,4]]\n"), ("[[1, 2], [3, 4]]".to_string(), 0));
+    assert_eq!(evaluate("m = [[1,2],[3,4]]; m[2,1]\n").0, "3");
+    assert_eq!(evaluate("m = [[1,2],[3,4]]; m[2]\n").0, "[3, 4]");
+}
+
+#[test]
+fn arithmetic() {
+    assert_eq!(evaluate("[[1,2],[3,4]] * [[5,6],[7,8]]\n").0, "[[19, 22], [43, 50]]");
+    assert_eq!(evaluate("[[1,2],[3,4]] + [[1,1],[1,1]]\n").0, "[[2, 3], [4, 5]]");
+    assert_eq!(evaluate("[[1,2],[3,4]] * 2\n").0, "[[2, 4], [6, 8]]");
+    assert_eq!(evaluate("[[1,2],[3,4]] * [1,1]\n").0, "[3, 7]");
+    assert_eq!(evaluate("[[1,2],[3,4]]^2\n").0, "[[7, 10], [15, 22]]");
+    assert_eq!(evaluate("[[1,2],[3,4]] ^ -1\n").0, "[[-2, 1], [1.5, -0.5]]");
+}
+
+#[test]
+fn linear_algebra() {
+    assert_eq!(evaluate("transpose([[1,2,3],[4,5,6]])\n").0, "[[1, 4], [2, 5], [3, 6]]");
+    assert_eq!(evaluate("det([[1,2],[3,4]])\n").0, "-2");
+    assert_eq!(evaluate("inverse([[1,2],[3,4]])\n").0, "[[-2, 1], [1.5, -0.5]]");
+    assert_eq!(evaluate("rank([[1,2],[2,4]])\n").0, "1");
+    assert_eq!(evaluate("linsolve([[2,1],[1,3]], [3,5])\n").0, "[0.8, 1.4]");
+    assert_eq!(evaluate("dot([1,2,3],[4,5,6])\n").0, "32");
+    assert_eq!(evaluate("cross([1,0,0],[0,1,0])\n").0, "[0, 0, 1]");
+}
+
+#[test]
+fn small_results_are_not_rounded_to_zero() {
+    assert_eq!(evaluate("det([[0.00001,0],[0,0.00001]])\n").0, "0.00000000010000000000000002");
+    assert_eq!(evaluate("dot([0.00001],[0.00001])\n").0, "0.00000000010000000000000002");
+    assert_eq!(evaluate("inverse([[0.00000000001,0],[0,0.00000000001]])\n").0, "[[100000000000, 0], [0, 100000000000]]");
+    assert_eq!(evaluate("linsolve([[1,2],[3,4]], [5,6])\n").0, "[-4, 4.5]");
+}
+
+#[test]
+fn shape_mismatches() {
+    assert_eq!(evaluate("[[1,2],[3,4]] * [[1,2,3]]\n"), (String::new(), 3));
+    assert_eq!(error("[[1,2],[3,4]] * [[1,2,3]]\n"), "Shape mismatch.");
+    assert_eq!(error("[[1,2],[3,4]] + [[1,2,3],[4,5,6]]\n"), "Shape mismatch.");
+    assert_eq!(error("det([[1,2,3],[4,5,6]])\n"), "Shape mismatch.");
+    assert_eq!(error("dot([1,2],[1,2,3])\n"), "Shape mismatch.");
+    assert_eq!(error("cross([1,2],[3,4])\n"), "Shape mismatch.");
+}
+
+#[test]
+fn errors() {
+    assert_eq!(error("inverse([[1,2],[2,4]])\n"), "Invalid operation.");
+    assert_eq!(error("linsolve([[1,2],[2,4]], [1,1])\n"), "Invalid operation.");
+    assert_eq!(error("m = [[1,2],[3,4]]; m[3,1]\n"), "Invalid operation.");
+    assert_eq!(error("det(3)\n"), "Invalid operation.");
+    assert_eq!(error("det([[\"a\",1],[1,1]])\n"), "Type error.");
+}
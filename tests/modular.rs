@@ -0,0 +1,54 @@
+mod common;
+
+use common::{error, evaluate};
+
+#[test]
+fn results_are_reduced() {
+    assert_eq!(evaluate("mod 7 { 3 * 5 }\n"), ("1".to_string(), 0));
+    assert_eq!(evaluate("mod 7 { 2 - 5 }\n").0, "4");
+    assert_eq!(evaluate("mod 1000000007 { 123456789 * 987654321 }\n").0, "259106859");
+    assert_eq!(evaluate("mod 7 { 10 } + 1\n").0, "4");
+}
+
+#[test]
+fn quotients_use_the_inverse() {
+    assert_eq!(evaluate("mod 7 { 1 / 3 }\n").0, "5");
+    assert_eq!(evaluate("mod 7 { 3^-1 }\n").0, "5");
+    assert_eq!(evaluate("mod 6 { 1 / 3 }\n").1, 3);
+}
+
+#[test]
+fn remainders_are_never_negative() {
+    assert_eq!(evaluate("mod 7 { -3 % 5 }\n").0, "2");
+    assert_eq!(evaluate("mod 7 { 3 % 0 }\n").1, 3);
+}
+
+#[test]
+fn exponents_are_plain_integers() {
+    assert_eq!(evaluate("mod 7 { 2^(7+1) }\n").0, "4");
+    assert_eq!(evaluate("mod 1000000007 { 3^(10^18) }\n").0, "246336683");
+    assert_eq!(evaluate("mod 7 { 2^3^2 }\n").0, "1");
+    assert_eq!(evaluate("mod 7 { (2^3)^2 }\n").0, "1");
+    assert_eq!(evaluate("mod 7 { sum(i, 1, 3, 2^i) }\n").0, "0");
+}
+
+#[test]
+fn blocks_assign() {
+    assert_eq!(evaluate("mod 7 { x = 2^10 }; x\n").0, "2");
+    assert_eq!(evaluate("mod 7 { x = 3 } + 1; x * x\n").0, "9");
+}
+
+#[test]
+fn non_integers_are_an_error() {
+    assert_eq!(error("mod 7 { 2.5*2 }\n"), "Invalid operation.");
+    assert_eq!(error("mod 7 { 2.5 }\n"), "Invalid operation.");
+    assert_eq!(error("mod 7 { 2^0.5 }\n"), "Invalid operation.");
+    assert_eq!(error("mod 0 { 1 }\n"), "Invalid operation.");
+    assert_eq!(error("mod 2.5 { 1 }\n"), "Invalid operation.");
+}
+
+#[test]
+fn unclosed_blocks_are_a_syntax_error() {
+    assert_eq!(evaluate("mod 7 { 1\n").1, 2);
+    assert_eq!(evaluate("mod 7 1 }\n").1, 2);
+}
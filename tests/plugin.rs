@@ -0,0 +1,99 @@
+#![cfg(unix)]
+
+mod common;
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use common::{error_with, evaluate_with};
+use rust_abacus::errors::ErrorType;
+use rust_abacus::executor::Executor;
+use rust_abacus::functions::plugin::PluginError;
+use rust_abacus::value::Value;
+
+//directory of a test holding the fixtures of tests/plugins compiled with cc
+fn plugin_directory(name: &str, fixtures: &[&str]) -> PathBuf {
+    let directory = env::temp_dir().join(format!("rust_abacus_{}_{}", name, std::process::id()));
+    fs::create_dir_all(&directory).unwrap();
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    for fixture in fixtures {
+        let status = Command::new("cc")
+            .args(["-shared", "-fPIC", "-I"])
+            .arg(root.join("include"))
+            .arg("-o").arg(directory.join(format!("{}.so", fixture)))
+            .arg(root.join("tests").join("plugins").join(format!("{}.c", fixture)))
+            .status()
+            .expect("Failed to run cc");
+        assert!(status.success(), "cannot compile {}", fixture);
+    }
+    directory
+}
+
+fn load(directory: &Path, fixture: &str) -> (Executor, Result<Vec<String>, PluginError>) {
+    let mut executor = Executor::new();
+    let loaded = executor.functions.load_plugin(&directory.join(format!("{}.so", fixture)));
+    (executor, loaded)
+}
+
+#[test]
+fn plugin_functions_are_called() {
+    let directory = plugin_directory("plugin_calls", &["tax"]);
+    let (mut executor, loaded) = load(&directory, "tax");
+    assert_eq!(loaded.unwrap(), vec!["tax", "total", "fail"]);
+    assert_eq!(executor.evaluate("tax(100)"), Ok(Value::Integer(20)));
+    assert_eq!(executor.evaluate("total(1, 2, 3.5)").unwrap().to_string(), "6.5");
+    assert_eq!(executor.evaluate("tax(\"a\")"), Err(ErrorType::TypeError));
+    assert_eq!(executor.evaluate("fail(1)"), Err(ErrorType::InvalidOperation));
+    assert_eq!(executor.evaluate("total(1, 2)"), Err(ErrorType::SyntaxError));
+
+    let flag = directory.display().to_string();
+    assert_eq!(evaluate_with(&["--plugins", &flag], "tax(50) + 1\n"), ("11".to_string(), 0));
+    assert_eq!(error_with(&["--plugins", &flag], "fail(1)\n"), "Invalid operation.");
+    fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn other_versions_are_refused() {
+    let directory = plugin_directory("plugin_version", &["future"]);
+    let (mut executor, loaded) = load(&directory, "future");
+    assert!(matches!(loaded, Err(PluginError::Version(2))));
+    assert_eq!(executor.evaluate("twice(1)"), Err(ErrorType::UnboundVariable));
+    fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn invalid_plugins_register_nothing() {
+    let directory = plugin_directory("plugin_invalid", &["no_entry", "clash", "no_call"]);
+    assert!(matches!(load(&directory, "no_entry").1, Err(PluginError::MissingEntryPoint)));
+    assert!(matches!(load(&directory, "no_call").1, Err(PluginError::InvalidTable(_))));
+    let (mut executor, loaded) = load(&directory, "clash");
+    assert!(matches!(loaded, Err(PluginError::Registration(name)) if name == "sqrt"));
+    assert_eq!(executor.evaluate("half(4)"), Err(ErrorType::UnboundVariable));
+    assert_eq!(executor.evaluate("sqrt(4)").unwrap().to_string(), "2");
+
+    fs::write(directory.join("garbage.so"), "not a library").unwrap();
+    assert!(matches!(load(&directory, "garbage").1, Err(PluginError::Open(_))));
+    assert!(matches!(load(&directory, "missing").1, Err(PluginError::Open(_))));
+    fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn failed_plugins_are_reported_and_the_others_load() {
+    let directory = plugin_directory("plugin_directory", &["tax", "future", "clash"]);
+    let flag = directory.display().to_string();
+    let (output, code) = evaluate_with(&["--plugins", &flag], "tax(10)\n");
+    let errors = error_with(&["--plugins", &flag], "tax(10)\n");
+    fs::remove_dir_all(&directory).unwrap();
+    assert_eq!((output, code), ("2".to_string(), 0));
+    assert!(errors.contains("future.so: plugin version 2, expected 1"), "{}", errors);
+    assert!(errors.contains("clash.so: cannot register sqrt"), "{}", errors);
+    assert!(!errors.contains("tax.so"), "{}", errors);
+}
+
+#[test]
+fn a_missing_directory_fails() {
+    let missing = env::temp_dir().join(format!("rust_abacus_plugin_missing_{}", std::process::id()));
+    let (_, code) = evaluate_with(&["--plugins", &missing.display().to_string()], "1\n");
+    assert_eq!(code, 1);
+}
@@ -0,0 +1,12 @@
+/* half registers, then sqrt clashes with a defined function, so neither is loaded */
+#include "abacus_plugin.h"
+
+static int half(const double *args, uint32_t count, double *result) {
+    *result = args[0] / 2;
+    return 0;
+}
+
+static const AbacusPluginFunction functions[] = {{"half", 1, half}, {"sqrt", 1, half}};
+static const AbacusPlugin plugin = {ABACUS_PLUGIN_VERSION, 2, functions};
+
+const AbacusPlugin *abacus_plugin(void) { return &plugin; }
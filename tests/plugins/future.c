@@ -0,0 +1,12 @@
+/* built against a later version of the table */
+#include "abacus_plugin.h"
+
+static int twice(const double *args, uint32_t count, double *result) {
+    *result = args[0] * 2;
+    return 0;
+}
+
+static const AbacusPluginFunction functions[] = {{"twice", 1, twice}};
+static const AbacusPlugin plugin = {ABACUS_PLUGIN_VERSION + 1, 1, functions};
+
+const AbacusPlugin *abacus_plugin(void) { return &plugin; }
@@ -0,0 +1,8 @@
+/* a function without a call and a null name */
+#include <stddef.h>
+#include "abacus_plugin.h"
+
+static const AbacusPluginFunction functions[] = {{"broken", 1, NULL}, {NULL, 1, NULL}};
+static const AbacusPlugin plugin = {ABACUS_PLUGIN_VERSION, 2, functions};
+
+const AbacusPlugin *abacus_plugin(void) { return &plugin; }
@@ -0,0 +1,2 @@
+/* a library without the abacus_plugin symbol */
+int twice(int x) { return x * 2; }
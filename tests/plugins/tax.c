@@ -0,0 +1,24 @@
+/* a valid plugin: tax(amount), total(a, b, c) and fail(x), which always fails */
+#include "abacus_plugin.h"
+
+static int tax(const double *args, uint32_t count, double *result) {
+    *result = args[0] * 0.2;
+    return 0;
+}
+
+static int total(const double *args, uint32_t count, double *result) {
+    *result = 0;
+    for (uint32_t i = 0; i < count; i++) {
+        *result += args[i];
+    }
+    return 0;
+}
+
+static int fail(const double *args, uint32_t count, double *result) {
+    return 1;
+}
+
+static const AbacusPluginFunction functions[] = {{"tax", 1, tax}, {"total", 3, total}, {"fail", 1, fail}};
+static const AbacusPlugin plugin = {ABACUS_PLUGIN_VERSION, 3, functions};
+
+const AbacusPlugin *abacus_plugin(void) { return &plugin; }
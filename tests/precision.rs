@@ -0,0 +1,65 @@
+mod common;
+
+use common::{error_with, evaluate, evaluate_with};
+
+fn at(flags: &[&str], input: &str) -> String {
+    evaluate_with(flags, input).0
+}
+
+#[test]
+fn decimals_are_exact() {
+    assert_eq!(evaluate("0.1 + 0.2\n"), ("0.3".to_string(), 0));
+    assert_eq!(evaluate("1.5 * 3\n").0, "4.5");
+}
+
+#[test]
+fn long_products_keep_their_significant_digits() {
+    assert_eq!(evaluate("0.1^30\n").0, "0.000000000000000000000000000001");
+    assert_eq!(evaluate("0.1^100\n").0, format!("0.{}1", "0".repeat(99)));
+    assert_eq!(evaluate("10.0^400\n").0, format!("1{}", "0".repeat(400)));
+    assert_eq!(evaluate("1.1^30\n").0, "17.4494022688864073185588037538");
+}
+
+#[test]
+fn only_the_result_is_rounded() {
+    assert_eq!(at(&["--precision", "1"], "1.05 + 1.05\n"), "2.1");
+    assert_eq!(at(&["--precision", "1"], "1.25*1.25\n"), "1.6");
+    assert_eq!(at(&["--precision", "0", "--rounding", "floor"], "0 - 2.5\n"), "-3");
+    assert_eq!(at(&["--precision", "0", "--rounding", "floor"], "0 - 5/2\n"), "-3");
+    assert_eq!(at(&["--precision", "2"], "x = 1.005; x\n"), "1.00");
+    assert_eq!(at(&["--precision", "2"], "pmt(0.05/12, 360, 200000)\n"), "1073.64");
+}
+
+#[test]
+fn rounding_modes() {
+    assert_eq!(at(&["--precision", "2"], "0.125 + 0\n"), "0.12");
+    assert_eq!(at(&["--precision", "2", "--rounding", "half-up"], "0.125 + 0\n"), "0.13");
+    assert_eq!(at(&["--precision", "2", "--rounding", "ceil"], "1/3\n"), "0.34");
+    assert_eq!(at(&["--precision", "2", "--rounding", "floor"], "2/3\n"), "0.66");
+}
+
+#[test]
+fn functions_and_powers_at_the_precision() {
+    assert_eq!(at(&["--precision", "30"], "2^0.5\n"), "1.414213562373095048801688724210");
+    assert_eq!(at(&["--precision", "50"], "sqrt(2)\n"), "1.41421356237309504880168872420969807856967187537695");
+    assert_eq!(at(&["--precision", "3"], "2^(1/3)\n"), "1.260");
+    assert_eq!(at(&["--precision", "4"], "exp(1)\n"), "2.7183");
+    assert_eq!(at(&["--precision", "4"], "ln(2)\n"), "0.6931");
+    assert_eq!(at(&["--precision", "50"], "exp(-100000)\n"), format!("0.{}", "0".repeat(50)));
+}
+
+#[test]
+fn every_result_shows_the_digits() {
+    assert_eq!(at(&["--precision", "3"], "1.5\n"), "1.500");
+    assert_eq!(at(&["--precision", "2"], "sin(1)\n"), "0.84");
+    assert_eq!(at(&["--precision", "2"], "[1/3, 2.555]\n"), "[0.33, 2.56]");
+    assert_eq!(at(&["--precision", "2"], "7\n"), "7");
+}
+
+#[test]
+fn errors() {
+    assert_eq!(error_with(&["--precision", "2"], "1/0\n"), "Invalid operation.");
+    assert_eq!(error_with(&["--precision", "2"], "ln(0)\n"), "Invalid operation.");
+    assert_eq!(evaluate_with(&["--precision", "x"], "1\n").1, 1);
+    assert_eq!(evaluate_with(&["--precision", "2", "--rounding", "up"], "1\n").1, 1);
+}
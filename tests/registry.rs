@@ -0,0 +1,107 @@
+mod common;
+
+use common::error;
+use rust_abacus::errors::{Error, ErrorType};
+use rust_abacus::executor::Executor;
+use rust_abacus::functions::{CalcFunction, FunctionRegistry};
+use rust_abacus::value::Value;
+
+//a fifth of an integer amount
+struct Tax;
+
+impl CalcFunction for Tax {
+    fn name(&self) -> &str {
+        "tax"
+    }
+
+    fn arity(&self) -> usize {
+        1
+    }
+
+    fn call(&self, args: &[Value]) -> Result<Value, ErrorType> {
+        match args {
+            [Value::Integer(amount)] => Ok(Value::Integer(amount / 5)),
+            _ => Error::throw(ErrorType::TypeError)
+        }
+    }
+}
+
+//a function with a given name and arity which returns its number of arguments
+struct Named(&'static str, usize);
+
+impl CalcFunction for Named {
+    fn name(&self) -> &str {
+        self.0
+    }
+
+    fn arity(&self) -> usize {
+        self.1
+    }
+
+    fn call(&self, args: &[Value]) -> Result<Value, ErrorType> {
+        Ok(Value::Integer(args.len() as i64))
+    }
+}
+
+fn with_tax() -> Executor {
+    let mut executor = Executor::new();
+    executor.functions.register(Tax).unwrap();
+    executor
+}
+
+#[test]
+fn registered_functions_are_called() {
+    let mut executor = with_tax();
+    assert_eq!(executor.evaluate("tax(100)"), Ok(Value::Integer(20)));
+    assert_eq!(executor.evaluate("tax(100) + tax(tax(250))"), Ok(Value::Integer(30)));
+    assert_eq!(executor.evaluate("a = 50; tax(a * 2)"), Ok(Value::Integer(20)));
+}
+
+#[test]
+fn every_argument_is_passed() {
+    let mut executor = Executor::new();
+    executor.functions.register(Named("count", 3)).unwrap();
+    executor.functions.register(Named("none", 0)).unwrap();
+    assert_eq!(executor.evaluate("count(1, 2 + 3, 4)"), Ok(Value::Integer(3)));
+    assert_eq!(executor.evaluate("none()"), Ok(Value::Integer(0)));
+}
+
+#[test]
+fn errors_of_a_call_fail_the_evaluation() {
+    let mut executor = with_tax();
+    assert_eq!(executor.evaluate("tax(\"a\")"), Err(ErrorType::TypeError));
+    assert_eq!(executor.evaluate("tax(1/2)"), Err(ErrorType::TypeError));
+}
+
+#[test]
+fn wrong_argument_counts_are_a_syntax_error() {
+    let mut executor = with_tax();
+    assert_eq!(executor.evaluate("tax(1, 2)"), Err(ErrorType::SyntaxError));
+    assert_eq!(executor.evaluate("tax()"), Err(ErrorType::SyntaxError));
+}
+
+#[test]
+fn reserved_and_defined_names_are_rejected() {
+    let mut registry = FunctionRegistry::new();
+    for name in ["sqrt", "let", "mod", "to", "", "1tax", "tax-rate"] {
+        assert_eq!(registry.register(Named(name, 1)), Err(ErrorType::InvalidOperation), "{}", name);
+    }
+    assert_eq!(registry.names().count(), 0);
+}
+
+#[test]
+fn registering_again_replaces_the_function() {
+    let mut executor = with_tax();
+    executor.functions.register(Named("tax", 2)).unwrap();
+    assert_eq!(executor.evaluate("tax(100, 1)"), Ok(Value::Integer(2)));
+    assert_eq!(executor.functions.names().collect::<Vec<_>>(), vec!["tax"]);
+}
+
+#[test]
+fn unregistered_names_are_unbound() {
+    let mut executor = with_tax();
+    assert!(executor.functions.unregister("tax"));
+    assert!(!executor.functions.unregister("tax"));
+    assert_eq!(executor.evaluate("tax(100)"), Err(ErrorType::UnboundVariable));
+    assert_eq!(error("tax(100)\n"), "Unbound variable.");
+}
@@ -0,0 +1,46 @@
+mod common;
+
+use std::env;
+use std::fs;
+use common::evaluate_with;
+
+//run a script file with content and return (stdout, exit code)
+fn run_script(name: &str, content: &str) -> (String, i32) {
+    let path = env::temp_dir().join(format!("rust_abacus_{}_{}.abacus", name, std::process::id()));
+    fs::write(&path, content).unwrap();
+    let output = evaluate_with(&["--script", &path.display().to_string()], "");
+    fs::remove_file(&path).unwrap();
+    output
+}
+
+#[test]
+fn every_statement_has_a_result() {
+    assert_eq!(run_script("statements", "a = 2\nb = a + 3\nb * 2; 7\n"), ("2\n5\n10\n7".to_string(), 0));
+}
+
+#[test]
+fn comments_are_skipped() {
+    assert_eq!(run_script("comments", "# a script\na = 2 # two\n// the end\na * 3 // six\n"), ("2\n6".to_string(), 0));
+}
+
+#[test]
+fn incomplete_line_continues_on_the_next() {
+    assert_eq!(run_script("continued", "b = 1 +\n  3\nmax(b,\n 2)\n"), ("4\n4".to_string(), 0));
+}
+
+#[test]
+fn blank_lines_are_not_statements() {
+    assert_eq!(run_script("blank", "\n1\n\n;\n2\n"), ("1\n2".to_string(), 0));
+}
+
+#[test]
+fn error_in_a_statement_fails_the_script() {
+    assert_eq!(run_script("syntax", "1\n1 +\n").1, 2);
+    assert_eq!(run_script("unbound", "1\nx\n").1, 3);
+}
+
+#[test]
+fn missing_script() {
+    let path = env::temp_dir().join("rust_abacus_missing.abacus").display().to_string();
+    assert_eq!(evaluate_with(&["--script", &path], "").1, 1);
+}
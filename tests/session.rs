@@ -0,0 +1,91 @@
+mod common;
+
+use std::env;
+use std::fs;
+use common::evaluate_with;
+
+//session file of a test, in the temporary directory
+fn session_path(name: &str) -> String {
+    env::temp_dir().join(format!("rust_abacus_{}_{}.session", name, std::process::id())).display().to_string()
+}
+
+#[test]
+fn saved_variables_are_loaded_back() {
+    let path = session_path("roundtrip");
+    evaluate_with(&["-repl"], &format!("a = 2\nb = 1/3\nt = \"x, \\\"y\\\"\"\nd = 2024-02-29\n:save {}\n", path));
+    let (output, code) = evaluate_with(&["-repl"], &format!(":load {}\na + 1\nb * 3\nt\nd\n", path));
+    fs::remove_file(&path).unwrap();
+    assert_eq!(output, "3\n1\nx, \"y\"\n2024-02-29");
+    assert_eq!(code, 0);
+}
+
+#[test]
+fn saved_numbers_keep_their_precision() {
+    let path = session_path("precision");
+    evaluate_with(&["-repl"], &format!("a = sqrt(2)\nm = [[1, 2], [3, sqrt(5)]]\nq = 1 km / 3\n:save {}\n", path));
+    let (output, _) = evaluate_with(&["-repl"], &format!(":load {}\na == sqrt(2)\nq * 3\n", path));
+    assert_eq!(output, "true\n1 km");
+    let content = fs::read_to_string(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert!(content.contains("a = float(1.4142135623730951)"));
+    assert!(content.contains("m = [[1, 2], [3, float(2.23606797749979)]]"));
+}
+
+#[test]
+fn load_flag_reads_a_session() {
+    let path = session_path("flag");
+    fs::write(&path, "# rust_abacus session\nx = 4\n").unwrap();
+    let output = evaluate_with(&["--load", &path], "x * 2\n");
+    fs::remove_file(&path).unwrap();
+    assert_eq!(output, ("8".to_string(), 0));
+}
+
+#[test]
+fn malformed_session_fails_to_load() {
+    let path = session_path("malformed");
+    fs::write(&path, "x = 1 +\n").unwrap();
+    let output = evaluate_with(&["--load", &path], "1\n");
+    fs::remove_file(&path).unwrap();
+    assert_eq!(output.1, 2);
+    assert_eq!(evaluate_with(&["--load", &session_path("missing")], "1\n").1, 1);
+}
+
+#[test]
+fn float_is_the_floating_point_number() {
+    assert_eq!(evaluate_with(&[], "float(0.5) * 2\n"), ("1".to_string(), 0));
+    assert_eq!(evaluate_with(&[], "float(\"a\")\n").1, 3);
+}
+
+#[test]
+fn complex_values_load_in_any_mode() {
+    let path = session_path("complex");
+    evaluate_with(&["-repl", "--complex"], &format!("z = sqrt(0-2) + 1\n:save {}\n", path));
+    let (output, code) = evaluate_with(&["-repl"], &format!(":load {}\nz\nz * z\n", path));
+    let content = fs::read_to_string(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(output, "1 + 1.4142135623730951i\n-1 + 2.8284271247461903i");
+    assert_eq!(code, 0);
+    assert!(content.contains("z = complex(float(1), float(1.4142135623730951))"));
+}
+
+#[test]
+fn compound_units_load_back() {
+    let path = session_path("units");
+    evaluate_with(&["-repl"], &format!("v = 5 km/h\nr = 3 s^-1\n:save {}\n", path));
+    let (output, code) = evaluate_with(&["-repl"], &format!(":load {}\nv * 2 h\nr\n", path));
+    fs::remove_file(&path).unwrap();
+    assert_eq!(output, "10 km\n3 s^-1");
+    assert_eq!(code, 0);
+}
+
+#[test]
+fn intervals_load_in_any_mode() {
+    let path = session_path("interval");
+    evaluate_with(&["-repl", "--interval"], &format!("e = 2 ± 0.1\n:save {}\n", path));
+    let (output, code) = evaluate_with(&["-repl"], &format!(":load {}\ne\n", path));
+    let content = fs::read_to_string(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(output, "[1.9, 2.1]");
+    assert_eq!(code, 0);
+    assert!(content.contains("e = float(2) ± float(0.10000000000000009)"));
+}
@@ -0,0 +1,46 @@
+mod common;
+
+use common::{evaluate, evaluate_with};
+
+#[test]
+fn identities_are_removed() {
+    assert_eq!(evaluate("simplify(x + 0)\n").0, "x");
+    assert_eq!(evaluate("simplify(1 * x)\n").0, "x");
+    assert_eq!(evaluate("simplify(x / 1)\n").0, "x");
+    assert_eq!(evaluate("simplify(x^1)\n").0, "x");
+    assert_eq!(evaluate("simplify(x * 0)\n").0, "0");
+    assert_eq!(evaluate("simplify(-(-x))\n").0, "x");
+}
+
+#[test]
+fn constants_are_folded() {
+    assert_eq!(evaluate("simplify(2 + 3 * 4)\n").0, "14");
+    assert_eq!(evaluate("simplify((2^3)^x)\n").0, "8^x");
+    assert_eq!(evaluate("simplify(sqrt(8))\n").0, "2 * sqrt(2)");
+}
+
+#[test]
+fn like_terms_are_collected() {
+    assert_eq!(evaluate("simplify(x + x)\n").0, "2 * x");
+    assert_eq!(evaluate("simplify(2*x + 3*x)\n").0, "5 * x");
+    assert_eq!(evaluate("simplify(x - x)\n").0, "0");
+}
+
+#[test]
+fn printer_writes_minimal_parentheses() {
+    assert_eq!(evaluate("simplify((a + b) * c)\n").0, "(a + b) * c");
+    assert_eq!(evaluate("simplify(a - (b - c))\n").0, "a - b + c");
+    assert_eq!(evaluate("simplify(a - (b + c))\n").0, "a - b - c");
+    assert_eq!(evaluate("simplify(2^(3^x))\n").0, "2^3^x");
+}
+
+#[test]
+fn simplify_mode_prints_every_statement() {
+    assert_eq!(evaluate_with(&["--simplify"], "x + 0\n(a+b)*(c)\n2*y+y\n"), ("x\n(a + b) * c\n3 * y".to_string(), 0));
+}
+
+#[test]
+fn invalid_simplify() {
+    assert_eq!(evaluate("simplify(x, y)\n").1, 3);
+    assert_eq!(evaluate_with(&["--simplify"], "1 +\n").1, 2);
+}
@@ -0,0 +1,46 @@
+mod common;
+
+use common::evaluate;
+
+#[test]
+fn exact_solutions() {
+    assert_eq!(evaluate("solve(2*x + 1 = 7, x)\n"), ("3".to_string(), 0));
+    assert_eq!(evaluate("solve(x^2 - 5*x + 6 = 0, x)\n").0, "[2, 3]");
+    assert_eq!(evaluate("solve(x^2 = 4, x)\n").0, "[-2, 2]");
+}
+
+#[test]
+fn numeric_solutions() {
+    assert_eq!(evaluate("solve(x^3 - x = 0, x)\n").0, "[-1, 0, 1]");
+    assert_eq!(evaluate("solve(x^3 + x + 1 = 0, x)\n").0, "-0.6823278038280193");
+}
+
+#[test]
+fn roots_beyond_the_first_bracket() {
+    assert_eq!(evaluate("solve(x^3 = 10^15, x)\n").0, "100000");
+    assert_eq!(evaluate("solve(x^3 = -10^15, x)\n").0, "-100000");
+    assert_eq!(evaluate("solve(exp(x/100) = 1000, x)\n").0, "690.7755278982137");
+}
+
+#[test]
+fn periodic_equations_give_the_roots_of_one_period() {
+    assert_eq!(evaluate("solve(sin(x) = 0.5, x)\n").0, "[0.5235987755982988, 2.6179938779914944]");
+    assert_eq!(evaluate("solve(sin(x) = 0, x)\n").0, "[0, 3.141592653589793]");
+    assert_eq!(evaluate("solve(cos(x) = -1, x)\n").0, "3.141592653589793");
+    assert_eq!(evaluate("solve(sin(x/2) = 0.5, x)\n").0, "[1.0471975511965976, 5.235987755982989]");
+    assert_eq!(evaluate("solve(sin(x) = 2, x)\n").1, 3);
+}
+
+#[test]
+fn no_root_is_an_error() {
+    assert_eq!(evaluate("solve(x^2 = -4, x)\n").1, 3);
+    assert_eq!(evaluate("solve(exp(x) = 0, x)\n").1, 3);
+    assert_eq!(evaluate("solve(1/x = 0, x)\n").1, 3);
+}
+
+#[test]
+fn other_names_and_bad_variables() {
+    assert_eq!(evaluate("solve(x + y = 1, x)\n").0, "-(y - 1)");
+    assert_eq!(evaluate("solve(x^3 + y = 1, x)\n").1, 3);
+    assert_eq!(evaluate("solve(x^2 = 4, 3)\n").1, 2);
+}
@@ -0,0 +1,46 @@
+mod common;
+
+use common::{error, evaluate};
+
+#[test]
+fn averages() {
+    assert_eq!(evaluate("mean([1,2,3,4])\n"), ("2.5".to_string(), 0));
+    assert_eq!(evaluate("median([3,1,2])\n").0, "2");
+    assert_eq!(evaluate("median([4,1,3,2])\n").0, "2.5");
+    assert_eq!(evaluate("mode([1,2,2,3])\n").0, "2");
+}
+
+#[test]
+fn spread() {
+    assert_eq!(evaluate("variance([1,2,3,4])\n").0, "1.6666666666666667");
+    assert_eq!(evaluate("pvariance([1,2,3,4])\n").0, "1.25");
+    assert_eq!(evaluate("stddev([2,4,4,4,5,5,7,9])\n").0, "2.138089935299395");
+    assert_eq!(evaluate("pstddev([2,4,4,4,5,5,7,9])\n").0, "2");
+    assert_eq!(evaluate("quantile([1,2,3,4,5], 0.25)\n").0, "2");
+}
+
+#[test]
+fn two_lists() {
+    assert_eq!(evaluate("correlation([1,2,3],[2,4,6])\n").0, "1");
+    assert_eq!(evaluate("linreg([1,2,3],[3,5,7])\n").0, "[2, 1]");
+}
+
+#[test]
+fn variadic_max_and_min() {
+    assert_eq!(evaluate("max(3, 1, 2)\n").0, "3");
+    assert_eq!(evaluate("min(3, 1, 2)\n").0, "1");
+    assert_eq!(evaluate("max([3,1,2])\n").0, "3");
+}
+
+#[test]
+fn errors() {
+    assert_eq!(evaluate("mean([\"a\"])\n"), (String::new(), 3));
+    assert_eq!(error("mean([\"a\"])\n"), "Type error.");
+    assert_eq!(error("mean([1, true])\n"), "Type error.");
+    assert_eq!(error("mean(\"abc\")\n"), "Type error.");
+    assert_eq!(error("max(1, \"a\")\n"), "Type error.");
+    assert_eq!(error("correlation([1,2],[1,2,3])\n"), "Shape mismatch.");
+    assert_eq!(error("mean([])\n"), "Invalid operation.");
+    assert_eq!(error("variance([1])\n"), "Invalid operation.");
+    assert_eq!(error("quantile([1,2,3], 2)\n"), "Invalid operation.");
+}
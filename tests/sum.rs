@@ -0,0 +1,36 @@
+mod common;
+
+use common::evaluate;
+
+#[test]
+fn sums_and_products() {
+    assert_eq!(evaluate("sum(i, 1, 100, i^2)\n"), ("338350".to_string(), 0));
+    assert_eq!(evaluate("prod(k, 2, 10, 1 - 1/k^2)\n").0, "11/20");
+    assert_eq!(evaluate("n = 5; prod(k, 1, n, k)\n").0, "120");
+    assert_eq!(evaluate("sum(i, 1, 3, i) * 2\n").0, "12");
+}
+
+#[test]
+fn empty_ranges() {
+    assert_eq!(evaluate("sum(i, 3, 1, i)\n").0, "0");
+    assert_eq!(evaluate("prod(i, 3, 1, i)\n").0, "1");
+}
+
+#[test]
+fn nested_sums_see_the_outer_index() {
+    assert_eq!(evaluate("sum(i, 1, 3, sum(j, 1, i, j))\n").0, "10");
+}
+
+#[test]
+fn the_index_does_not_leak() {
+    assert_eq!(evaluate("sum(i, 1, 3, i); i\n").1, 3);
+    assert_eq!(evaluate("i = 7; sum(i, 1, 3, i); i\n").0, "7");
+}
+
+#[test]
+fn errors() {
+    assert_eq!(evaluate("sum(i, 1, 2.5, i)\n").1, 3);
+    assert_eq!(evaluate("sum(i, 1, 3)\n").1, 3);
+    assert_eq!(evaluate("sum(i, 1, 3, y)\n").1, 3);
+    assert_eq!(evaluate("sum(3, 1, 2, 1)\n").1, 2);
+}
@@ -0,0 +1,61 @@
+mod common;
+
+use common::{error, evaluate};
+
+#[test]
+fn literals_and_concatenation() {
+    assert_eq!(evaluate("\"ab\" + \"cd\"\n"), ("abcd".to_string(), 0));
+    assert_eq!(evaluate("\"a\\\"b\"\n").0, "a\"b");
+    assert_eq!(evaluate("\"a\" == \"a\"\n").0, "true");
+    assert_eq!(evaluate("t = \"x\"; t + str(1)\n").0, "x1");
+}
+
+#[test]
+fn str_and_format() {
+    assert_eq!(evaluate("str(1/3)\n").0, "1/3");
+    assert_eq!(evaluate("str(2.5)\n").0, "2.5");
+    assert_eq!(evaluate("format(\"{:.2}\", 3.14159)\n").0, "3.14");
+    assert_eq!(evaluate("format(\"{}\", 7)\n").0, "7");
+    assert_eq!(evaluate("format(\"x {} y\", 1)\n").0, "x 1 y");
+    assert_eq!(evaluate("format(\"{:.2}\", 1/8)\n").0, "0.13");
+    assert_eq!(evaluate("format(\"{:.2}\", 2/3)\n").0, "0.67");
+    assert_eq!(evaluate("format(\"{:6.2}\", 0 - 1/8)\n").0, "-0.13");
+}
+
+#[test]
+fn width_and_decimals_are_capped() {
+    assert_eq!(evaluate("len(format(\"{:1000.1000}\", 1))\n").0, "1002");
+    assert_eq!(error("format(\"{:1001}\", 1)\n"), "Invalid operation.");
+    assert_eq!(error("format(\"{:99999999999}\", 1)\n"), "Invalid operation.");
+    assert_eq!(error("format(\"{:.3000000000}\", 1)\n"), "Invalid operation.");
+}
+
+#[test]
+fn len_hex_and_bin() {
+    assert_eq!(evaluate("len(\"héllo\")\n").0, "5");
+    assert_eq!(evaluate("len([1, 2, 3])\n").0, "3");
+    assert_eq!(evaluate("hex(255)\n").0, "0xff");
+    assert_eq!(evaluate("hex(-1)\n").0, "-0x1");
+    assert_eq!(evaluate("bin(5)\n").0, "0b101");
+}
+
+#[test]
+fn text_in_arithmetic_is_a_type_error() {
+    assert_eq!(error("\"a\" * 2\n"), "Type error.");
+    assert_eq!(error("\"a\" + 1\n"), "Type error.");
+    assert_eq!(error("-\"a\"\n"), "Type error.");
+    assert_eq!(error("sqrt(\"a\")\n"), "Type error.");
+}
+
+#[test]
+fn wrong_arguments_are_a_type_error() {
+    assert_eq!(error("format(\"{:.2}\", \"a\")\n"), "Type error.");
+    assert_eq!(error("hex(2.5)\n"), "Type error.");
+    assert_eq!(error("len(5)\n"), "Type error.");
+    assert_eq!(error("format(\"{} {}\", 1)\n"), "Invalid operation.");
+}
+
+#[test]
+fn unterminated_text_is_a_syntax_error() {
+    assert_eq!(error("\"abc\n"), "Syntax error.");
+}
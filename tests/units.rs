@@ -0,0 +1,64 @@
+mod common;
+
+use common::{error, evaluate};
+
+#[test]
+fn quantities_after_numbers() {
+    assert_eq!(evaluate("3 km + 200 m\n"), ("3.2 km".to_string(), 0));
+    assert_eq!(evaluate("2 m * 3 m\n").0, "6 m^2");
+    assert_eq!(evaluate("1 km / 3\n").0, "0.333333333333 km");
+    assert_eq!(evaluate("sqrt(4 m^2)\n").0, "2 m");
+    assert_eq!(evaluate("(2 m)^2\n").0, "4 m^2");
+    assert_eq!(evaluate("[1 m, 2 m]\n").0, "[1 m, 2 m]");
+}
+
+#[test]
+fn a_number_binds_to_its_unit_first() {
+    assert_eq!(evaluate("10 m / 2 m\n").0, "5");
+    assert_eq!(evaluate("10 m / 2 s\n").0, "5 m/s");
+    assert_eq!(evaluate("6 / 2 m\n").0, "3 m^-1");
+    assert_eq!(evaluate("-3 m\n").0, "-3 m");
+}
+
+#[test]
+fn minutes_and_inches() {
+    assert_eq!(evaluate("30 min\n").0, "30 min");
+    assert_eq!(evaluate("1 h to min\n").0, "60 min");
+    assert_eq!(evaluate("90 km/min to km/h\n").0, "5400 km/h");
+    assert_eq!(evaluate("3 in to cm\n").0, "7.62 cm");
+    assert_eq!(evaluate("min(3, 4)\n").0, "3");
+    assert_eq!(evaluate("let x = 3 in x\n").0, "3");
+}
+
+#[test]
+fn compound_units_are_kept_as_written() {
+    assert_eq!(evaluate("3 m/s^2\n").0, "3 m/s^2");
+    assert_eq!(evaluate("3 s^-1\n").0, "3 s^-1");
+    assert_eq!(evaluate("3 m/s^2 * 2 s\n").0, "6 m/s");
+    assert_eq!(evaluate("100 km/h * 2 h\n").0, "200 km");
+}
+
+#[test]
+fn conversions_show_the_target_unit() {
+    assert_eq!(evaluate("1 km to m\n").0, "1000 m");
+    assert_eq!(evaluate("1 N to kg*m/s^2\n").0, "1 kg*m/s^2");
+    assert_eq!(evaluate("5 km/h to m/s\n").0, "1.38888888889 m/s");
+    assert_eq!(evaluate("1 kWh to J\n").0, "3600000 J");
+    assert_eq!(evaluate("x = 3 m; x to cm\n").0, "300 cm");
+}
+
+#[test]
+fn unbound_names_are_not_units() {
+    assert_eq!(evaluate("a = m\n"), (String::new(), 3));
+    assert_eq!(error("a = m\n"), "Unbound variable.");
+    assert_eq!(error("x = s + 1\n"), "Unbound variable.");
+    assert_eq!(error("3 kg*x\n"), "Unbound variable.");
+    assert_eq!(evaluate("m = 5; 2*m\n").0, "10");
+}
+
+#[test]
+fn dimension_mismatches() {
+    assert_eq!(error("1 km + 1\n"), "Dimension mismatch.");
+    assert_eq!(error("1 m to s\n"), "Dimension mismatch.");
+    assert_eq!(error("sin(1 m)\n"), "Dimension mismatch.");
+}
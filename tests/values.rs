@@ -0,0 +1,74 @@
+mod common;
+
+use common::{error, evaluate};
+
+#[test]
+fn integers_promote_to_fractions() {
+    assert_eq!(evaluate("1/2 + 1/3\n"), ("5/6".to_string(), 0));
+    assert_eq!(evaluate("6/3\n").0, "2");
+    assert_eq!(evaluate("-1/3\n").0, "-1/3");
+    assert_eq!(evaluate("(1/3)^2\n").0, "1/9");
+    assert_eq!(evaluate("(1/3)^-2\n").0, "9");
+    assert_eq!(evaluate("1/3 % 1/4\n").0, "1/12");
+}
+
+#[test]
+fn decimals_with_fractions_stay_exact() {
+    assert_eq!(evaluate("1/3 + 0.25\n").0, "7/12");
+    assert_eq!(evaluate("1/3 + 1.5\n").0, "11/6");
+    assert_eq!(evaluate("0.5 / (1/3)\n").0, "3/2");
+}
+
+#[test]
+fn fractions_and_floats_promote_to_floats() {
+    assert_eq!(evaluate("1/3 + float(0.25)\n").0, "0.5833333333333333");
+    assert_eq!(evaluate("(2/3) ^ (1/2)\n").0, "0.816496580927726");
+}
+
+#[test]
+fn integers_past_the_i64_range_stay_exact() {
+    assert_eq!(evaluate("9223372036854775807 + 1\n").0, "9223372036854775808");
+    assert_eq!(evaluate("2^62 * 4\n").0, "18446744073709551616");
+    assert_eq!(evaluate("2^100\n").0, "1267650600228229401496703205376");
+    assert_eq!(evaluate("2^100 + 1\n").0, "1267650600228229401496703205377");
+}
+
+#[test]
+fn results_past_floating_point_numbers_are_invalid() {
+    assert_eq!(error("2^100000000000\n"), "Invalid operation.");
+    assert_eq!(error("exp(1000)\n"), "Invalid operation.");
+    assert_eq!(error("exp(1000.5)\n"), "Invalid operation.");
+    assert_eq!(error("2^-2000\n"), "Invalid operation.");
+}
+
+#[test]
+fn small_floats_are_not_rounded_to_zero() {
+    assert_eq!(evaluate("exp(-30)\n").0, "0.00000000000009357622968840175");
+    assert_eq!(evaluate("exp(-30) > 0\n").0, "true");
+}
+
+#[test]
+fn comparisons_are_exact() {
+    assert_eq!(evaluate("1/3 == 0.3333333333333333\n").0, "false");
+    assert_eq!(evaluate("1/3 < 0.3333333333333334\n").0, "true");
+    assert_eq!(evaluate("0.3333333333333333 < 1/3\n").0, "true");
+    assert_eq!(evaluate("0.25 == 1/4\n").0, "true");
+    assert_eq!(evaluate("0.1 + 0.2 == 3/10\n").0, "true");
+    assert_eq!(evaluate("1/3 < 0.333333333333333333333333334\n").0, "true");
+    assert_eq!(evaluate("1 == 1.0\n").0, "true");
+    assert_eq!(evaluate("true == true\n").0, "true");
+}
+
+#[test]
+fn mismatched_types_are_a_type_error() {
+    assert_eq!(error("true + 1\n"), "Type error.");
+    assert_eq!(error("true < false\n"), "Type error.");
+    assert_eq!(error("\"a\" < 1\n"), "Type error.");
+}
+
+#[test]
+fn division_by_zero_is_invalid() {
+    assert_eq!(error("1/0\n"), "Invalid operation.");
+    assert_eq!(error("(1/3) / 0\n"), "Invalid operation.");
+    assert_eq!(error("0.5 % (0/3)\n"), "Invalid operation.");
+}